@@ -1,7 +1,90 @@
+mod alphabet;
 mod atbase;
-mod affine;
+mod cryptanalysis;
+pub(crate) mod affine;
 mod rotational;
 mod diffie_hellman;
+mod playfair;
+mod polybius;
 mod rail_fence;
+mod random;
+mod transposition;
+mod registry;
+mod vigenere;
 
 const PLAIN: &str = "abcdefghijklmnopqrstuvwxyz";
+
+// Interface commune des chiffrements classiques, la clé est portée par l'implémentation
+trait Cipher {
+    fn encrypt(&self, plain: &str) -> String;
+    fn decrypt(&self, cipher: &str) -> String;
+}
+
+impl<C: Cipher + ?Sized> Cipher for Box<C> {
+    fn encrypt(&self, plain: &str) -> String {
+        (**self).encrypt(plain)
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        (**self).decrypt(cipher)
+    }
+}
+
+// Présentation du texte chiffré : d'un seul tenant ou par blocs séparés d'une espace
+// (5 lettres par tradition, ce qui masque la longueur des mots).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grouping {
+    Continuous,
+    Blocks(usize),
+}
+
+impl Grouping {
+    // Les blancs existants sont retirés avant le découpage
+    fn apply(self, text: &str) -> String {
+        match self {
+            Grouping::Continuous => text.to_string(),
+            Grouping::Blocks(0) => text.chars().filter(|c| !c.is_whitespace()).collect(),
+            Grouping::Blocks(size) => text.chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<Vec<char>>()
+                .chunks(size).map(|chunk| chunk.iter().collect::<String>())
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+}
+
+// Adaptateur appliquant un regroupement à la sortie de n'importe quel chiffrement.
+// Au déchiffrement, les séparateurs de blocs sont retirés avant de passer la main.
+struct Grouped<C> {
+    cipher: C,
+    grouping: Grouping,
+}
+
+impl<C: Cipher> Grouped<C> {
+    fn new(cipher: C, grouping: Grouping) -> Self {
+        Grouped { cipher, grouping }
+    }
+}
+
+impl<C: Cipher> Cipher for Grouped<C> {
+    fn encrypt(&self, plain: &str) -> String {
+        self.grouping.apply(&self.cipher.encrypt(plain))
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        match self.grouping {
+            Grouping::Continuous => self.cipher.decrypt(cipher),
+            Grouping::Blocks(_) => self.cipher.decrypt(&Grouping::Blocks(0).apply(cipher)),
+        }
+    }
+}
+
+#[test]
+fn grouping_blocks() {
+    assert_eq!(Grouping::Blocks(5).apply("nrmwyoldrmtob"), "nrmwy oldrm tob");
+    assert_eq!(Grouping::Blocks(3).apply("nrm wyo ldrm"), "nrm wyo ldr m");
+    assert_eq!(Grouping::Blocks(0).apply("a b  c"), "abc");
+    assert_eq!(Grouping::Continuous.apply("a b"), "a b");
+    assert_eq!(Grouping::Blocks(4).apply(""), "");
+}
@@ -1,10 +1,47 @@
-use crate::cypher::PLAIN;
+use crate::cypher::{alphabet::Alphabet, Cipher, Grouping, PLAIN};
+#[cfg(test)]
+use crate::cypher::alphabet::{Case, Unknown, FRENCH};
 
 #[derive(Debug, Eq, PartialEq)]
-enum AffineCipherError {
+pub(crate) enum AffineCipherError {
     NotCoprime(i32),
 }
 
+// Clé (`a`, `b`) vérifiée à la construction contre la longueur de l'alphabet,
+// l'inverse de a est calculé une seule fois
+pub(crate) struct Affine {
+    alphabet: Alphabet,
+    a: i64,
+    b: i64,
+    a_inv: i64,
+}
+
+impl Affine {
+    pub(crate) fn new(a: i32, b: i32, alphabet: Alphabet) -> Result<Self, AffineCipherError> {
+        let m = alphabet.len() as i32;
+        let a_inv = modinv(a, m)? as i64;
+        // Réduction modulo m pour que ax + b ne puisse pas dépasser i64
+        Ok(Affine { a: a.rem_euclid(m) as i64, b: b.rem_euclid(m) as i64, a_inv, alphabet })
+    }
+
+    fn transform(&self, text: &str, f: impl Fn(i64) -> i64) -> String {
+        let m = self.alphabet.len() as i64;
+        self.alphabet.map(text, |r| f(r as i64).rem_euclid(m) as usize)
+    }
+}
+
+impl Cipher for Affine {
+    // E(x) = (ax + b) mod m
+    fn encrypt(&self, plain: &str) -> String {
+        self.transform(plain, |x| self.a * x + self.b)
+    }
+
+    // D(y) = a^-1(y - b) mod m
+    fn decrypt(&self, cipher: &str) -> String {
+        self.transform(cipher, |y| self.a_inv * (y - self.b))
+    }
+}
+
 // Encodes the plaintext using the affine cipher with key (`a`, `b`).
 // E(x) = (ax + b) mod m
 
@@ -13,23 +50,8 @@ enum AffineCipherError {
 // a and b are integers which make the encryption key
 // a and m are coprime or error
 fn encode(plaintext: &str, a: u32, b: u32) -> Result<String, AffineCipherError> {
-    let _ = modinv(a as i32, PLAIN.len() as i32)?;
-    let x = plaintext.chars()
-                    .filter(|c| c.is_ascii_alphanumeric())
-                    .map(|c| c.to_ascii_lowercase())
-                    .map(|pt|
-                        if pt.is_ascii_digit() { pt }
-                        else {
-                            let pos = (a as usize * PLAIN.chars().position(|bs| bs == pt).unwrap() + b as usize) % PLAIN.len();
-                            PLAIN.chars().nth(pos).unwrap()
-                        }
-                    )
-                    .collect::<Vec<char>>()
-                    .chunks(5).map(|chunk| chunk.iter().collect::<String>())
-                    .collect::<Vec<String>>()
-                    .join(" ");
-
-    Ok(x)
+    let cipher = Affine::new(a as i32, (b % PLAIN.len() as u32) as i32, Alphabet::latin())?;
+    Ok(Grouping::Blocks(5).apply(&cipher.encrypt(plaintext)))
 }
 
 // Decodes the ciphertext using the affine cipher with key (`a`, `b`).
@@ -40,23 +62,8 @@ fn encode(plaintext: &str, a: u32, b: u32) -> Result<String, AffineCipherError>
 // y = E(x) is the numeric value of an encrypted letter
 // it is important to note that a^-1 is the modular multiplicative inverse (MMI) of a mod m
 // the modular multiplicative inverse only exists if a and m are coprime.
-// if index calculated from the letter encrypted is negative then add m
 fn decode(ciphertext: &str, a: i32, b: i32) -> Result<String, AffineCipherError> {
-    let a_prime = modinv(a, PLAIN.len() as i32)?;
-    let y = ciphertext.chars()
-                    .filter(|c| c.is_ascii_alphanumeric())
-                    .map(|c| c.to_ascii_lowercase())
-                    .map(|ct|{
-                        if ct.is_ascii_digit() { ct }
-                        else {
-                            let mut n = (a_prime * (PLAIN.chars().position(|bs| bs == ct).unwrap() as i32 - b)) % PLAIN.len() as i32;
-                            n = if n < 0 { PLAIN.len() as i32 + n } else { n };
-                            PLAIN.chars().nth(n as usize).unwrap()
-                        }
-                    })
-                    .collect::<String>();
-
-    Ok(y)
+    Ok(Affine::new(a, b, Alphabet::latin())?.decrypt(ciphertext))
 }
 
 // Calculate the MMI of a and m
@@ -64,18 +71,18 @@ fn decode(ciphertext: &str, a: i32, b: i32) -> Result<String, AffineCipherError>
 // ax mod m = 1
 // return x such that (x * a) % b == 1
 fn modinv(a: i32, m: i32) -> Result<i32, AffineCipherError> {
-    let (g, x, _) = xgcd(a, m);
+    let (g, x, _) = xgcd(a as i64, m as i64);
     if g != 1 {
         Err(AffineCipherError::NotCoprime(a))
     } else {
-        Ok(x % m)
+        Ok((x % m as i64) as i32)
     }
 }
 
 // find the pgcd of a and b with extended euclidian
 // return (g, x, y) such that a*x + b*y = g = gcd(a, b)
 // g must to equal to 1
-fn xgcd(a: i32, b: i32) -> (i32, i32, i32) {
+pub(crate) fn xgcd(a: i64, b: i64) -> (i64, i64, i64) {
     let mut x = (0, 1);
     let mut y = (1, 0);
     let mut q = 0;
@@ -218,4 +225,15 @@ fn decode_with_a_not_coprime_to_m() {
     let output = decode(phrase, a, b);
     let expected = Err(AffineCipherError::NotCoprime(13));
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+#[test]
+fn affine_with_french_alphabet() {
+    // 42 lettres : a doit être premier avec 2, 3 et 7
+    let french = || Alphabet::new(FRENCH, Case::Preserve, Unknown::Keep).unwrap();
+    assert!(matches!(Affine::new(7, 3, french()), Err(AffineCipherError::NotCoprime(7))));
+    assert!(Affine::new(7, 3, Alphabet::latin()).is_ok());
+    let cipher = Affine::new(5, 8, french()).unwrap();
+    let text = "Où est passée la forêt ?";
+    assert_eq!(cipher.decrypt(&cipher.encrypt(text)), text);
+    assert_ne!(cipher.encrypt(text), text);
+}
@@ -0,0 +1,170 @@
+// Alphabet des chiffrements classiques : un ensemble ordonné de symboles Unicode,
+// le rang de chaque symbole sert aux calculs (décalage, affine, inversion…).
+
+use std::collections::HashMap;
+
+use super::PLAIN;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Error {
+    EmptyAlphabet,
+    DuplicateSymbol(char),
+}
+
+// Traitement de la casse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Case {
+    // Recherche en minuscules, sortie en minuscules
+    Fold,
+    // Recherche en minuscules, la sortie reprend la casse de l'entrée
+    Preserve,
+    // Correspondance exacte, l'alphabet peut contenir majuscules et minuscules
+    Sensitive,
+}
+
+// Traitement des caractères absents de l'alphabet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unknown {
+    Keep,
+    Drop,
+    // Les chiffres ASCII 0-9 passent, tout le reste est supprimé (y compris ², ٣ ou Ⅻ)
+    KeepDigits,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Alphabet {
+    symbols: Vec<char>,
+    ranks: HashMap<char, usize>,
+    case: Case,
+    unknown: Unknown,
+}
+
+// Minuscule d'un caractère quand elle tient en un seul caractère
+fn lower(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+impl Alphabet {
+    pub(crate) fn new(symbols: &str, case: Case, unknown: Unknown) -> Result<Self, Error> {
+        let symbols = symbols.chars()
+            .map(|c| if case == Case::Sensitive { c } else { lower(c) })
+            .collect::<Vec<char>>();
+        if symbols.is_empty() { return Err(Error::EmptyAlphabet); }
+        let mut ranks = HashMap::with_capacity(symbols.len());
+        for (i, c) in symbols.iter().enumerate() {
+            if ranks.insert(*c, i).is_some() { return Err(Error::DuplicateSymbol(*c)); }
+        }
+
+        Ok(Alphabet { symbols, ranks, case, unknown })
+    }
+
+    // Comportement historique d'atbash et affine : a-z en minuscules, chiffres conservés
+    pub(crate) fn latin() -> Self {
+        Alphabet::new(PLAIN, Case::Fold, Unknown::KeepDigits).unwrap()
+    }
+
+    // Comportement historique de rotate : casse conservée, ponctuation et espaces inchangés
+    pub(crate) fn latin_preserving() -> Self {
+        Alphabet::new(PLAIN, Case::Preserve, Unknown::Keep).unwrap()
+    }
+
+    // Alphabet à mot-clé : les symboles du mot-clé sans doublon, puis le reste dans l'ordre.
+    // Les caractères du mot-clé absents de l'alphabet sont ignorés.
+    pub(crate) fn keyed(&self, keyword: &str) -> Self {
+        let mut symbols = Vec::with_capacity(self.len());
+        for rank in keyword.chars().filter_map(|c| self.rank(c)).chain(0..self.len()) {
+            if !symbols.contains(&self.symbols[rank]) { symbols.push(self.symbols[rank]); }
+        }
+        let ranks = symbols.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+        Alphabet { symbols, ranks, ..self.clone() }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub(crate) fn symbol(&self, rank: usize) -> char {
+        self.symbols[rank % self.len()]
+    }
+
+    pub(crate) fn rank(&self, c: char) -> Option<usize> {
+        match self.case {
+            Case::Sensitive => self.ranks.get(&c).copied(),
+            _ => self.ranks.get(&lower(c)).copied(),
+        }
+    }
+
+    // Substitution symbole par symbole : f reçoit le rang d'origine et renvoie le nouveau rang.
+    // f n'est appelée que pour les symboles de l'alphabet, dans l'ordre du texte.
+    pub(crate) fn map(&self, text: &str, mut f: impl FnMut(usize) -> usize) -> String {
+        let mut output = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.rank(c) {
+                Some(rank) => {
+                    let symbol = self.symbol(f(rank));
+                    if self.case == Case::Preserve && c != lower(c) {
+                        output.extend(symbol.to_uppercase());
+                    } else {
+                        output.push(symbol);
+                    }
+                },
+                None => match self.unknown {
+                    Unknown::Keep => output.push(c),
+                    Unknown::KeepDigits if c.is_ascii_digit() => output.push(c),
+                    _ => (),
+                },
+            }
+        }
+        output
+    }
+}
+
+// Alphabet français complet, partagé par les tests des chiffrements
+#[cfg(test)]
+pub(crate) const FRENCH: &str = "abcdefghijklmnopqrstuvwxyzàâæçéèêëîïôœùûüÿ";
+
+#[test]
+fn alphabet_errors() {
+    assert_eq!(Alphabet::new("", Case::Fold, Unknown::Keep).err(), Some(Error::EmptyAlphabet));
+    assert_eq!(Alphabet::new("abca", Case::Fold, Unknown::Keep).err(), Some(Error::DuplicateSymbol('a')));
+    assert_eq!(Alphabet::new("aA", Case::Fold, Unknown::Keep).err(), Some(Error::DuplicateSymbol('a')));
+    assert!(Alphabet::new("aA", Case::Sensitive, Unknown::Keep).is_ok());
+}
+#[test]
+fn alphabet_case_policies() {
+    let shift = |r: usize| r + 1;
+    assert_eq!(Alphabet::new(PLAIN, Case::Fold, Unknown::Keep).unwrap().map("Hal 9000!", shift), "ibm 9000!");
+    assert_eq!(Alphabet::new(PLAIN, Case::Preserve, Unknown::Keep).unwrap().map("Hal 9000!", shift), "Ibm 9000!");
+    assert_eq!(Alphabet::new(PLAIN, Case::Sensitive, Unknown::Keep).unwrap().map("Hal 9000!", shift), "Hbm 9000!");
+}
+#[test]
+fn alphabet_unknown_policies() {
+    let same = |r: usize| r;
+    assert_eq!(Alphabet::new(PLAIN, Case::Fold, Unknown::Keep).unwrap().map("a-1 b", same), "a-1 b");
+    assert_eq!(Alphabet::new(PLAIN, Case::Fold, Unknown::Drop).unwrap().map("a-1 b", same), "ab");
+    assert_eq!(Alphabet::new(PLAIN, Case::Fold, Unknown::KeepDigits).unwrap().map("a-1 b", same), "a1b");
+    assert_eq!(Alphabet::latin().map("x² ٣ Ⅻ 42", same), "x42");
+}
+#[test]
+fn alphabet_with_accented_letters() {
+    let french = Alphabet::new(FRENCH, Case::Preserve, Unknown::Keep).unwrap();
+    assert_eq!(french.len(), 42);
+    assert_eq!(french.rank('É'), Some(30));
+    assert_eq!(french.map("Œuvre à Noël", |r| r + 1), "Ùvwsf â Opîm");
+}
+#[test]
+fn keyword_alphabet() {
+    let keyed = Alphabet::latin().keyed("Zebras!");
+    assert_eq!((0..26).map(|r| keyed.symbol(r)).collect::<String>(), "zebrascdfghijklmnopqtuvwxy");
+    assert_eq!(keyed.rank('Z'), Some(0));
+    assert_eq!(Alphabet::latin().keyed("").rank('q'), Some(16));
+}
+#[test]
+fn alphabet_other_scripts() {
+    let greek = Alphabet::new("αβγδεζηθικλμνξοπρστυφχψω", Case::Preserve, Unknown::Keep).unwrap();
+    assert_eq!(greek.map("Ωμέγα", |r| r + 1), "Ανέδβ");
+}
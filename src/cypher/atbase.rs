@@ -1,29 +1,59 @@
-use std::collections::HashMap;
-use crate::cypher::PLAIN;
+use crate::cypher::{alphabet::Alphabet, Cipher, Grouping};
+#[cfg(test)]
+use crate::cypher::alphabet::{Case, Unknown, FRENCH};
 
-const CIPHER: &str = "zyxwvutsrqponmlkjihgfedcba";
+// https://en.wikipedia.org/wiki/Substitution_cipher#Simple_substitution
+// Substitution monoalphabétique : chaque rang de l'alphabet est remplacé par un autre,
+// selon une permutation fixe. Atbash et l'alphabet à mot-clé en sont deux cas particuliers.
+pub(crate) struct Substitution {
+    alphabet: Alphabet,
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+}
+
+impl Substitution {
+    fn new(alphabet: Alphabet, forward: Vec<usize>) -> Self {
+        let mut backward = vec![0; forward.len()];
+        for (p, c) in forward.iter().enumerate() {
+            backward[*c] = p;
+        }
+        Substitution { alphabet, forward, backward }
+    }
+
+    // https://en.wikipedia.org/wiki/Atbash
+    // L'alphabet est lu à l'envers : le rang r devient n - 1 - r
+    pub(crate) fn atbash(alphabet: Alphabet) -> Self {
+        let forward = (0..alphabet.len()).rev().collect();
+        Substitution::new(alphabet, forward)
+    }
+
+    // https://en.wikipedia.org/wiki/Keyword_cipher
+    // La i-ème lettre de l'alphabet est remplacée par la i-ème de l'alphabet à mot-clé
+    pub(crate) fn keyword(keyword: &str, alphabet: Alphabet) -> Self {
+        let keyed = alphabet.keyed(keyword);
+        let forward = (0..alphabet.len()).map(|r| alphabet.rank(keyed.symbol(r)).unwrap()).collect();
+        Substitution::new(alphabet, forward)
+    }
+}
+
+impl Cipher for Substitution {
+    fn encrypt(&self, plain: &str) -> String {
+        self.alphabet.map(plain, |r| self.forward[r])
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        self.alphabet.map(cipher, |r| self.backward[r])
+    }
+}
 
 /// "Encipher" with the Atbash cipher.
 fn encode(plain: &str) -> String {
-    let base_pairing: HashMap<char, char> = HashMap::from_iter(PLAIN.chars().zip(CIPHER.chars()));
-    plain.chars()
-    .filter(|c| c.is_ascii_alphanumeric())
-    .map(|c| c.to_ascii_lowercase())
-    .map(|c| if c.is_ascii_digit() { c } else { base_pairing[&c] })
-    .collect::<Vec<char>>()
-    .chunks(5).map(|chunk| chunk.iter().collect::<String>())
-    .collect::<Vec<String>>()
-    .join(" ")
+    Grouping::Blocks(5).apply(&Substitution::atbash(Alphabet::latin()).encrypt(plain))
 }
 
 /// "Decipher" with the Atbash cipher.
 fn decode(cipher: &str) -> String {
-    let base_pairing: HashMap<char, char> = HashMap::from_iter(CIPHER.chars().zip(PLAIN.chars()));
-    cipher.chars()
-    .filter(|c| c.is_ascii_alphanumeric())
-    .map(|c| c.to_ascii_lowercase())
-    .map(|c| if c.is_ascii_digit() { c } else { base_pairing[&c] })
-    .collect::<String>()
+    Substitution::atbash(Alphabet::latin()).decrypt(cipher)
 }
 
 #[test]
@@ -96,4 +126,25 @@ fn decode_with_no_spaces() {
         decode("zmlyhgzxovrhlugvmzhgvkkrmthglmv"),
         "anobstacleisoftenasteppingstone",
     );
-}
\ No newline at end of file
+}
+#[test]
+fn atbash_with_french_alphabet() {
+    let alphabet = Alphabet::new(FRENCH, Case::Preserve, Unknown::Keep).unwrap();
+    let cipher = Substitution::atbash(alphabet);
+    assert_eq!(cipher.encrypt("Été"), "Lwl");
+    assert_eq!(cipher.decrypt("Lwl"), "Été");
+}
+#[test]
+fn keyword_substitution() {
+    let cipher = Substitution::keyword("kryptos", Alphabet::latin_preserving());
+    assert_eq!(cipher.encrypt("Knowledge is power"), "Dghvetpst bm ihvtl");
+    assert_eq!(cipher.decrypt("Dghvetpst bm ihvtl"), "Knowledge is power");
+}
+#[test]
+fn keyword_substitution_round_trip() {
+    let alphabet = Alphabet::new(FRENCH, Case::Preserve, Unknown::Keep).unwrap();
+    let cipher = Substitution::keyword("Noël à Sète", alphabet);
+    let text = "Où est passée la bûche de Noël ?";
+    assert_ne!(cipher.encrypt(text), text);
+    assert_eq!(cipher.decrypt(&cipher.encrypt(text)), text);
+}
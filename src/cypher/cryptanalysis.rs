@@ -0,0 +1,447 @@
+// https://en.wikipedia.org/wiki/Frequency_analysis
+// https://en.wikipedia.org/wiki/Index_of_coincidence
+// https://en.wikipedia.org/wiki/Kasiski_examination
+//
+// Chaque attaque renvoie des candidats triés du plus au moins vraisemblable :
+// le score est une distance à la langue visée, plus il est faible, meilleur il est.
+
+use std::collections::HashMap;
+
+use crate::cypher::{
+    affine::Affine,
+    alphabet::Alphabet,
+    rail_fence::RailFence,
+    rotational::Rotation,
+    vigenere::{Kind, Vigenere},
+    Cipher, PLAIN,
+};
+
+// Fréquences des lettres a-z en pourcentage, accents ramenés à la lettre de base pour le français
+const ENGLISH: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+const FRENCH: [f64; 26] = [
+    8.13, 0.93, 3.15, 3.55, 15.10, 0.96, 0.97, 1.08, 6.94, 0.71, 0.16, 5.68, 3.23, 6.42, 5.27,
+    3.03, 0.89, 6.43, 7.91, 7.11, 6.05, 1.83, 0.04, 0.42, 0.19, 0.21,
+];
+
+// Digrammes les plus fréquents : les transpositions conservent les fréquences des lettres,
+// seul l'ordre des lettres permet de les départager.
+const ENGLISH_BIGRAMS: [&str; 30] = [
+    "th", "he", "in", "er", "an", "re", "nd", "at", "on", "nt", "ha", "es", "st", "en", "ed", "to",
+    "it", "ou", "ea", "hi", "is", "or", "ti", "as", "te", "et", "ng", "of", "al", "de",
+];
+const FRENCH_BIGRAMS: [&str; 30] = [
+    "es", "le", "de", "en", "re", "nt", "on", "er", "te", "el", "an", "se", "et", "la", "ai", "it",
+    "me", "ou", "em", "ie", "qu", "ne", "ur", "ce", "ns", "ra", "ue", "is", "us", "ll",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    English,
+    French,
+}
+
+impl Language {
+    fn frequencies(self) -> &'static [f64; 26] {
+        match self {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+        }
+    }
+
+    fn bigrams(self) -> &'static [&'static str; 30] {
+        match self {
+            Language::English => &ENGLISH_BIGRAMS,
+            Language::French => &FRENCH_BIGRAMS,
+        }
+    }
+
+    // Indice de coïncidence attendu : probabilité que 2 lettres tirées au hasard soient égales
+    fn coincidence(self) -> f64 {
+        self.frequencies().iter().map(|f| (f / 100.0).powi(2)).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Candidate {
+    plaintext: String,
+    key: String,
+    score: f64,
+}
+
+// Lettre de base d'une lettre accentuée
+fn fold(c: char) -> char {
+    match c.to_lowercase().next().unwrap_or(c) {
+        'à' | 'â' | 'ä' => 'a',
+        'ç' => 'c',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'î' | 'ï' => 'i',
+        'ô' | 'ö' => 'o',
+        'ù' | 'û' | 'ü' => 'u',
+        'ÿ' => 'y',
+        l => l,
+    }
+}
+
+// Rangs 0-25 des lettres du texte, tout le reste est ignoré
+fn letters(text: &str) -> Vec<usize> {
+    text.chars()
+        .map(fold)
+        .filter(|c| c.is_ascii_lowercase())
+        .map(|c| (c as u8 - b'a') as usize)
+        .collect()
+}
+
+fn counts(ranks: &[usize]) -> [usize; 26] {
+    ranks.iter().fold([0; 26], |mut acc, r| {
+        acc[*r] += 1;
+        acc
+    })
+}
+
+fn chi_squared_ranks(ranks: &[usize], language: Language) -> f64 {
+    if ranks.is_empty() {
+        return f64::INFINITY;
+    }
+    let total = ranks.len() as f64;
+    counts(ranks)
+        .iter()
+        .zip(language.frequencies())
+        .map(|(observed, f)| {
+            let expected = total * f / 100.0;
+            (*observed as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+// χ² entre les fréquences observées et celles de la langue
+fn chi_squared(text: &str, language: Language) -> f64 {
+    chi_squared_ranks(&letters(text), language)
+}
+
+fn index_of_coincidence_ranks(ranks: &[usize]) -> f64 {
+    if ranks.len() < 2 {
+        return 0.0;
+    }
+    let n = ranks.len() as f64;
+    counts(ranks)
+        .iter()
+        .map(|c| (c * c.saturating_sub(1)) as f64)
+        .sum::<f64>()
+        / (n * (n - 1.0))
+}
+
+fn index_of_coincidence(text: &str) -> f64 {
+    index_of_coincidence_ranks(&letters(text))
+}
+
+// Part des digrammes courants de la langue, en négatif pour garder « plus faible = meilleur »
+fn bigram_score(text: &str, language: Language) -> f64 {
+    let ranks = letters(text);
+    if ranks.len() < 2 {
+        return 0.0;
+    }
+    let common = language
+        .bigrams()
+        .iter()
+        .map(|b| letters(b))
+        .collect::<Vec<Vec<usize>>>();
+    let hits = ranks
+        .windows(2)
+        .filter(|w| common.iter().any(|b| b[..] == w[..]))
+        .count();
+    -(hits as f64) / (ranks.len() - 1) as f64
+}
+
+fn ranked(mut candidates: Vec<Candidate>) -> Vec<Candidate> {
+    candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+    candidates
+}
+
+// Les 26 décalages de `rotate`
+fn break_rotation(cipher: &str, language: Language) -> Vec<Candidate> {
+    ranked(
+        (0..PLAIN.len())
+            .map(|key| {
+                let plaintext = Rotation::new(key, Alphabet::latin_preserving()).decrypt(cipher);
+                let score = chi_squared(&plaintext, language);
+                Candidate {
+                    plaintext,
+                    key: key.to_string(),
+                    score,
+                }
+            })
+            .collect(),
+    )
+}
+
+// Tous les couples (a, b) avec a premier avec 26
+fn break_affine(cipher: &str, language: Language) -> Vec<Candidate> {
+    let m = PLAIN.len() as i32;
+    ranked(
+        (1..m)
+            .flat_map(|a| (0..m).map(move |b| (a, b)))
+            .filter_map(|(a, b)| Affine::new(a, b, Alphabet::latin()).ok().map(|c| (a, b, c)))
+            .map(|(a, b, cipher_ab)| {
+                let plaintext = cipher_ab.decrypt(cipher);
+                let score = chi_squared(&plaintext, language);
+                Candidate {
+                    plaintext,
+                    key: format!("{a},{b}"),
+                    score,
+                }
+            })
+            .collect(),
+    )
+}
+
+// Examen de Kasiski : distances entre les répétitions de trigrammes du chiffré.
+// Pour chaque longueur de clé possible, le nombre de distances qu'elle divise (décroissant).
+fn kasiski(cipher: &str, max_len: usize) -> Vec<(usize, usize)> {
+    let ranks = letters(cipher);
+    let mut last_seen = HashMap::<&[usize], usize>::new();
+    let mut distances = Vec::new();
+    for (i, trigram) in ranks.windows(3).enumerate() {
+        if let Some(previous) = last_seen.insert(trigram, i) {
+            distances.push(i - previous);
+        }
+    }
+    let mut lengths = (2..=max_len)
+        .map(|len| {
+            (
+                len,
+                distances.iter().filter(|d| d.is_multiple_of(len)).count(),
+            )
+        })
+        .collect::<Vec<(usize, usize)>>();
+    lengths.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    lengths
+}
+
+// Indice de coïncidence moyen des colonnes pour chaque longueur de clé, par ordre décroissant :
+// avec la bonne longueur chaque colonne est un simple décalage et garde l'indice de la langue,
+// sinon les colonnes mélangent plusieurs décalages et tendent vers 1/26.
+fn key_lengths(cipher: &str, max_len: usize) -> Vec<(usize, f64)> {
+    let ranks = letters(cipher);
+    let mut lengths = (1..=max_len.min(ranks.len()))
+        .map(|len| {
+            let average = (0..len)
+                .map(|col| {
+                    index_of_coincidence_ranks(
+                        &ranks
+                            .iter()
+                            .skip(col)
+                            .step_by(len)
+                            .copied()
+                            .collect::<Vec<usize>>(),
+                    )
+                })
+                .sum::<f64>()
+                / len as f64;
+            (len, average)
+        })
+        .collect::<Vec<(usize, f64)>>();
+    lengths.sort_by(|a, b| b.1.total_cmp(&a.1));
+    lengths
+}
+
+// Plus courte période d'une clé ("lemonlemon" -> "lemon")
+fn shortest_period(key: &str) -> String {
+    let chars = key.chars().collect::<Vec<char>>();
+    let period = (1..=chars.len())
+        .find(|p| {
+            chars.len().is_multiple_of(*p)
+                && chars.iter().enumerate().all(|(i, c)| *c == chars[i % p])
+        })
+        .unwrap_or(chars.len());
+    chars[..period].iter().collect()
+}
+
+// Longueurs de clé à essayer : les trois meilleures selon l'indice de coïncidence, puis celles
+// de Kasiski qui ne figurent pas déjà parmi elles. Sur un texte court les colonnes sont trop
+// petites pour l'indice, alors que quelques trigrammes répétés suffisent à Kasiski.
+fn candidate_lengths(cipher: &str, max_len: usize) -> Vec<usize> {
+    let by_coincidence = key_lengths(cipher, max_len)
+        .into_iter()
+        .take(3)
+        .map(|(len, _)| len);
+    let by_repetition = kasiski(cipher, max_len)
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .take(3)
+        .map(|(len, _)| len);
+    let mut lengths = Vec::new();
+    for len in by_coincidence.chain(by_repetition) {
+        if !lengths.contains(&len) {
+            lengths.push(len);
+        }
+    }
+    lengths
+}
+
+// Pour les longueurs de clé les plus probables, chaque colonne est un simple décalage :
+// on retient celui qui minimise le χ².
+fn break_vigenere(cipher: &str, max_len: usize, language: Language) -> Vec<Candidate> {
+    let ranks = letters(cipher);
+    let mut candidates = Vec::<Candidate>::new();
+    for len in candidate_lengths(cipher, max_len) {
+        let key = (0..len)
+            .map(|col| {
+                let column = ranks
+                    .iter()
+                    .skip(col)
+                    .step_by(len)
+                    .copied()
+                    .collect::<Vec<usize>>();
+                let shift = (0..26)
+                    .min_by(|a, b| {
+                        let score = |k: usize| {
+                            chi_squared_ranks(
+                                &column
+                                    .iter()
+                                    .map(|c| (c + 26 - k) % 26)
+                                    .collect::<Vec<usize>>(),
+                                language,
+                            )
+                        };
+                        score(*a).total_cmp(&score(*b))
+                    })
+                    .unwrap();
+                (b'a' + shift as u8) as char
+            })
+            .collect::<String>();
+        let key = shortest_period(&key);
+        if candidates.iter().any(|c| c.key == key) {
+            continue;
+        }
+        let plaintext = Vigenere::new(&key, Kind::Vigenere, Alphabet::latin_preserving())
+            .unwrap()
+            .decrypt(cipher);
+        let score = chi_squared(&plaintext, language);
+        candidates.push(Candidate {
+            plaintext,
+            key,
+            score,
+        });
+    }
+    ranked(candidates)
+}
+
+// Nombre de rails de 2 à max_rails, classés sur les digrammes
+fn break_rail_fence(cipher: &str, max_rails: u32, language: Language) -> Vec<Candidate> {
+    let len = cipher.chars().count() as u32;
+    ranked(
+        (2..=max_rails.min(len.saturating_sub(1)))
+            .map(|rails| {
+                let plaintext = RailFence::new(rails).decrypt(cipher);
+                let score = bigram_score(&plaintext, language);
+                Candidate {
+                    plaintext,
+                    key: rails.to_string(),
+                    score,
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+const SAMPLE: &str =
+    "It was the best of times, it was the worst of times, it was the age of wisdom, \
+    it was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, \
+    it was the season of light, it was the season of darkness, it was the spring of hope, \
+    it was the winter of despair, we had everything before us, we had nothing before us.";
+#[cfg(test)]
+const ECHANTILLON: &str =
+    "Longtemps, je me suis couché de bonne heure. Parfois, à peine ma bougie éteinte, \
+    mes yeux se fermaient si vite que je n'avais pas le temps de me dire : je m'endors.";
+
+#[test]
+fn chi_squared_prefers_the_right_language() {
+    assert!(chi_squared(SAMPLE, Language::English) < chi_squared(SAMPLE, Language::French));
+    assert!(
+        chi_squared(ECHANTILLON, Language::French) < chi_squared(ECHANTILLON, Language::English)
+    );
+    assert_eq!(chi_squared("1234 !", Language::English), f64::INFINITY);
+}
+#[test]
+fn index_of_coincidence_of_english() {
+    assert!((index_of_coincidence(SAMPLE) - Language::English.coincidence()).abs() < 0.01);
+    assert_eq!(index_of_coincidence("abcdefghijklmnopqrstuvwxyz"), 0.0);
+    assert_eq!(index_of_coincidence("aaaa"), 1.0);
+}
+#[test]
+fn rotation_is_broken() {
+    let cipher = Rotation::new(17, Alphabet::latin_preserving()).encrypt(SAMPLE);
+    let best = &break_rotation(&cipher, Language::English)[0];
+    assert_eq!(best.key, "17");
+    assert_eq!(best.plaintext, SAMPLE);
+    let chiffre = Rotation::new(3, Alphabet::latin_preserving()).encrypt(ECHANTILLON);
+    assert_eq!(break_rotation(&chiffre, Language::French)[0].key, "3");
+}
+#[test]
+fn affine_is_broken() {
+    let cipher = Affine::new(5, 8, Alphabet::latin())
+        .unwrap()
+        .encrypt(SAMPLE);
+    let candidates = break_affine(&cipher, Language::English);
+    assert_eq!(candidates.len(), 12 * 26);
+    assert_eq!(candidates[0].key, "5,8");
+    assert!(candidates[0].plaintext.starts_with("itwasthebestoftimes"));
+}
+#[test]
+fn kasiski_finds_the_key_length() {
+    let cipher = Vigenere::new("lemon", Kind::Vigenere, Alphabet::latin_preserving())
+        .unwrap()
+        .encrypt(SAMPLE);
+    let lengths = kasiski(&cipher, 12);
+    assert_eq!(lengths[0].0, 5);
+    assert_eq!(key_lengths(&cipher, 12)[0].0 % 5, 0);
+    assert!(candidate_lengths(&cipher, 12).contains(&5));
+}
+#[test]
+fn kasiski_lengths_are_tried_too() {
+    let cipher = Vigenere::new("lemon", Kind::Vigenere, Alphabet::latin_preserving())
+        .unwrap()
+        .encrypt(SAMPLE);
+    let lengths = candidate_lengths(&cipher, 12);
+    let by_coincidence = key_lengths(&cipher, 12)
+        .into_iter()
+        .take(3)
+        .map(|(len, _)| len)
+        .collect::<Vec<usize>>();
+    assert_eq!(lengths[..3], by_coincidence);
+    assert!(kasiski(&cipher, 12)
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .take(3)
+        .all(|(len, _)| lengths.contains(len)));
+}
+#[test]
+fn vigenere_is_broken() {
+    let cipher = Vigenere::new("lemon", Kind::Vigenere, Alphabet::latin_preserving())
+        .unwrap()
+        .encrypt(SAMPLE);
+    let best = &break_vigenere(&cipher, 12, Language::English)[0];
+    assert_eq!(best.key, "lemon");
+    assert_eq!(best.plaintext, SAMPLE);
+}
+#[test]
+fn rail_fence_is_broken() {
+    let text = SAMPLE
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect::<String>();
+    let cipher = RailFence::new(4).encrypt(&text);
+    let best = &break_rail_fence(&cipher, 10, Language::English)[0];
+    assert_eq!(best.key, "4");
+    assert_eq!(best.plaintext, text);
+}
+#[test]
+fn shortest_key_period() {
+    assert_eq!(shortest_period("lemonlemon"), "lemon");
+    assert_eq!(shortest_period("aaa"), "a");
+    assert_eq!(shortest_period("abcab"), "abcab");
+}
@@ -1,50 +1,291 @@
-// cf https://en.wikipedia.org/wiki/Modular_exponentiation
-fn modular_exponentiation(base: &u64, exponent: &u64, modulus: &u64) -> u64 {
-    if *modulus == 1 { return 0; }
-    let modulus: u128 = *modulus as u128;
-    assert!((modulus - 1).checked_pow(2) != None);
+// cf https://en.wikipedia.org/wiki/Diffie%E2%80%93Hellman_key_exchange
+// Les calculs se font sur des entiers de taille arbitraire ; les fonctions sur u64
+// sont conservées pour les petits exemples.
 
-    let mut base: u128 = *base as u128 % modulus;
-    let mut exponent = *exponent;
-    let mut result: u128 = 1;
+use std::io;
 
-    while exponent > 0 {
-        if exponent % 2 == 1 {
-            result = (result * base) % modulus;
-        }
-        exponent = exponent >> 1;
-        base = (base).pow(2) % modulus;
-    }
-    result as u64
+use crate::{
+    cypher::random::{OsRandom, RandomSource},
+    numbers::{
+        big_uint::BigUint,
+        prime::miller_rabin::{is_probable_prime, SMALL_PRIMES},
+    },
+};
+#[cfg(test)]
+use crate::cypher::random::{assert_uniform, SeededRandom};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Error {
+    UnknownGroup(u32),
+    InvalidGroup,
+    OutOfRange,
+    NotInSubgroup,
 }
 
-fn random() -> u64 {
-    let mut x = 0;
-    unsafe {
-        core::arch::x86_64::_rdrand64_step(&mut x);
+// Groupes MODP de la RFC 3526 (https://www.rfc-editor.org/rfc/rfc3526), générateur 2.
+// p = 2^n - 2^(n-64) - 1 + 2^64 · (⌊2^(n-130) · π⌋ + k), premier sûr : (p - 1) / 2 est premier.
+const MODP_1536: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA237327 FFFFFFFF FFFFFFFF",
+);
+
+const MODP_2048: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B ",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718 ",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AACAA68 FFFFFFFF FFFFFFFF",
+);
+
+const MODP_3072: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B ",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718 ",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AAAC42D AD33170D 04507A33 ",
+    "A85521AB DF1CBA64 ECFB8504 58DBEF0A 8AEA7157 5D060C7D B3970F85 A6E1E4C7 ",
+    "ABF5AE8C DB0933D7 1E8C94E0 4A25619D CEE3D226 1AD2EE6B F12FFA06 D98A0864 ",
+    "D8760273 3EC86A64 521F2B18 177B200C BBE11757 7A615D6C 770988C0 BAD946E2 ",
+    "08E24FA0 74E5AB31 43DB5BFC E0FD108E 4B82D120 A93AD2CA FFFFFFFF FFFFFFFF",
+);
+
+const MODP_4096: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B ",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718 ",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AAAC42D AD33170D 04507A33 ",
+    "A85521AB DF1CBA64 ECFB8504 58DBEF0A 8AEA7157 5D060C7D B3970F85 A6E1E4C7 ",
+    "ABF5AE8C DB0933D7 1E8C94E0 4A25619D CEE3D226 1AD2EE6B F12FFA06 D98A0864 ",
+    "D8760273 3EC86A64 521F2B18 177B200C BBE11757 7A615D6C 770988C0 BAD946E2 ",
+    "08E24FA0 74E5AB31 43DB5BFC E0FD108E 4B82D120 A9210801 1A723C12 A787E6D7 ",
+    "88719A10 BDBA5B26 99C32718 6AF4E23C 1A946834 B6150BDA 2583E9CA 2AD44CE8 ",
+    "DBBBC2DB 04DE8EF9 2E8EFC14 1FBECAA6 287C5947 4E6BC05D 99B2964F A090C3A2 ",
+    "233BA186 515BE7ED 1F612970 CEE2D7AF B81BDD76 2170481C D0069127 D5B05AA9 ",
+    "93B4EA98 8D8FDDC1 86FFB7DC 90A6C08F 4DF435C9 34063199 FFFFFFFF FFFFFFFF",
+);
+
+const MODP_6144: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B ",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718 ",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AAAC42D AD33170D 04507A33 ",
+    "A85521AB DF1CBA64 ECFB8504 58DBEF0A 8AEA7157 5D060C7D B3970F85 A6E1E4C7 ",
+    "ABF5AE8C DB0933D7 1E8C94E0 4A25619D CEE3D226 1AD2EE6B F12FFA06 D98A0864 ",
+    "D8760273 3EC86A64 521F2B18 177B200C BBE11757 7A615D6C 770988C0 BAD946E2 ",
+    "08E24FA0 74E5AB31 43DB5BFC E0FD108E 4B82D120 A9210801 1A723C12 A787E6D7 ",
+    "88719A10 BDBA5B26 99C32718 6AF4E23C 1A946834 B6150BDA 2583E9CA 2AD44CE8 ",
+    "DBBBC2DB 04DE8EF9 2E8EFC14 1FBECAA6 287C5947 4E6BC05D 99B2964F A090C3A2 ",
+    "233BA186 515BE7ED 1F612970 CEE2D7AF B81BDD76 2170481C D0069127 D5B05AA9 ",
+    "93B4EA98 8D8FDDC1 86FFB7DC 90A6C08F 4DF435C9 34028492 36C3FAB4 D27C7026 ",
+    "C1D4DCB2 602646DE C9751E76 3DBA37BD F8FF9406 AD9E530E E5DB382F 413001AE ",
+    "B06A53ED 9027D831 179727B0 865A8918 DA3EDBEB CF9B14ED 44CE6CBA CED4BB1B ",
+    "DB7F1447 E6CC254B 33205151 2BD7AF42 6FB8F401 378CD2BF 5983CA01 C64B92EC ",
+    "F032EA15 D1721D03 F482D7CE 6E74FEF6 D55E702F 46980C82 B5A84031 900B1C9E ",
+    "59E7C97F BEC7E8F3 23A97A7E 36CC88BE 0F1D45B7 FF585AC5 4BD407B2 2B4154AA ",
+    "CC8F6D7E BF48E1D8 14CC5ED2 0F8037E0 A79715EE F29BE328 06A1D58B B7C5DA76 ",
+    "F550AA3D 8A1FBFF0 EB19CCB1 A313D55C DA56C9EC 2EF29632 387FE8D7 6E3C0468 ",
+    "043E8F66 3F4860EE 12BF2D5B 0B7474D6 E694F91E 6DCC4024 FFFFFFFF FFFFFFFF",
+);
+
+const MODP_8192: &str = concat!(
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74 ",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437 ",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED ",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05 ",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB ",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B ",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718 ",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AAAC42D AD33170D 04507A33 ",
+    "A85521AB DF1CBA64 ECFB8504 58DBEF0A 8AEA7157 5D060C7D B3970F85 A6E1E4C7 ",
+    "ABF5AE8C DB0933D7 1E8C94E0 4A25619D CEE3D226 1AD2EE6B F12FFA06 D98A0864 ",
+    "D8760273 3EC86A64 521F2B18 177B200C BBE11757 7A615D6C 770988C0 BAD946E2 ",
+    "08E24FA0 74E5AB31 43DB5BFC E0FD108E 4B82D120 A9210801 1A723C12 A787E6D7 ",
+    "88719A10 BDBA5B26 99C32718 6AF4E23C 1A946834 B6150BDA 2583E9CA 2AD44CE8 ",
+    "DBBBC2DB 04DE8EF9 2E8EFC14 1FBECAA6 287C5947 4E6BC05D 99B2964F A090C3A2 ",
+    "233BA186 515BE7ED 1F612970 CEE2D7AF B81BDD76 2170481C D0069127 D5B05AA9 ",
+    "93B4EA98 8D8FDDC1 86FFB7DC 90A6C08F 4DF435C9 34028492 36C3FAB4 D27C7026 ",
+    "C1D4DCB2 602646DE C9751E76 3DBA37BD F8FF9406 AD9E530E E5DB382F 413001AE ",
+    "B06A53ED 9027D831 179727B0 865A8918 DA3EDBEB CF9B14ED 44CE6CBA CED4BB1B ",
+    "DB7F1447 E6CC254B 33205151 2BD7AF42 6FB8F401 378CD2BF 5983CA01 C64B92EC ",
+    "F032EA15 D1721D03 F482D7CE 6E74FEF6 D55E702F 46980C82 B5A84031 900B1C9E ",
+    "59E7C97F BEC7E8F3 23A97A7E 36CC88BE 0F1D45B7 FF585AC5 4BD407B2 2B4154AA ",
+    "CC8F6D7E BF48E1D8 14CC5ED2 0F8037E0 A79715EE F29BE328 06A1D58B B7C5DA76 ",
+    "F550AA3D 8A1FBFF0 EB19CCB1 A313D55C DA56C9EC 2EF29632 387FE8D7 6E3C0468 ",
+    "043E8F66 3F4860EE 12BF2D5B 0B7474D6 E694F91E 6DBE1159 74A3926F 12FEE5E4 ",
+    "38777CB6 A932DF8C D8BEC4D0 73B931BA 3BC832B6 8D9DD300 741FA7BF 8AFC47ED ",
+    "2576F693 6BA42466 3AAB639C 5AE4F568 3423B474 2BF1C978 238F16CB E39D652D ",
+    "E3FDB8BE FC848AD9 22222E04 A4037C07 13EB57A8 1A23F0C7 3473FC64 6CEA306B ",
+    "4BCBC886 2F8385DD FA9D4B7F A2C087E8 79683303 ED5BDD3A 062B3CF5 B3A278A6 ",
+    "6D2A13F8 3F44F82D DF310EE0 74AB6A36 4597E899 A0255DC1 64F31CC5 0846851D ",
+    "F9AB4819 5DED7EA1 B1D510BD 7EE74D73 FAF36BC3 1ECFA268 359046F4 EB879F92 ",
+    "4009438B 481C6CD7 889A002E D5EE382B C9190DA6 FC026E47 9558E447 5677E9AA ",
+    "9E3050E2 765694DF C81F56E8 80B96E71 60C980DD 98EDD3DF FFFFFFFF FFFFFFFF",
+);
+
+// Plus petit premier sûr p = 2q + 1 ≥ start, avec q impair (donc p ≥ 7)
+fn next_safe_prime(start: &BigUint) -> BigUint {
+    let mut q = start.div_rem_small(2).0.max(BigUint::from(3));
+    if q.is_even() { q = &q + &BigUint::one(); }
+    let two = BigUint::from(2);
+    loop {
+        let p = &q.mul_small(2) + &BigUint::one();
+        // q et 2q + 1 ne doivent avoir aucun petit facteur : crible avant Miller-Rabin.
+        // Jusqu'au plus grand petit premier, q pourrait en être un lui-même : pas de crible.
+        let sieved = q <= BigUint::from(SMALL_PRIMES[23] as u128) || SMALL_PRIMES.iter().all(|prime| {
+            let r = q.div_rem_small(*prime).1;
+            r != 0 && (2 * r + 1) % prime != 0
+        });
+        if sieved && is_probable_prime(&q) && is_probable_prime(&p) { return p; }
+        q = &q + &two;
     }
-    x
 }
 
-fn random_at_most(max: u64) -> u64 {
-    let num_bins: u64 = max;
-    let bin_size = u64::MAX / num_bins;
-    let defect   = u64::MAX % num_bins;
-    let mut x: u64 = 0;
+// Entier uniforme dans [0, bound) : tirage du bon nombre de bits, rejeté s'il dépasse bound
+fn random_below(bound: &BigUint, source: &mut impl RandomSource) -> io::Result<BigUint> {
+    assert!(!bound.is_zero(), "intervalle vide");
+    let bits = bound.bits().len();
     loop {
-        x = random();
-        if u64::MAX - defect > x {
-            x /= bin_size;
-            if x >= 2 && x < max {
-                break;
-            }
+        let candidate = random_bits(bits, source)?;
+        if candidate < *bound { return Ok(candidate); }
+    }
+}
+
+// Entier d'au plus `bits` bits, par mots de 32 bits du poids fort au poids faible
+fn random_bits(bits: usize, source: &mut impl RandomSource) -> io::Result<BigUint> {
+    (0..bits.div_ceil(32)).try_fold(BigUint::zero(), |acc, i| {
+        let width = if i == 0 && !bits.is_multiple_of(32) { bits % 32 } else { 32 };
+        let word = source.next_u64()? >> (64 - width);
+        Ok(&acc.mul_small(1 << 32) + &BigUint::from(word as u128))
+    })
+}
+
+// Paramètres publics : p premier sûr, g engendre le sous-groupe d'ordre premier q = (p - 1) / 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Group {
+    p: BigUint,
+    g: BigUint,
+    q: BigUint,
+}
+
+impl Group {
+    // p et q = (p - 1) / 2 doivent être premiers (Miller-Rabin), et g d'ordre q
+    pub(crate) fn new(p: BigUint, g: BigUint) -> Result<Self, Error> {
+        if p < BigUint::from(7) || p.is_even() { return Err(Error::InvalidGroup); }
+        let q = p.div_rem_small(2).0;
+        if !is_probable_prime(&q) || !is_probable_prime(&p) { return Err(Error::InvalidGroup); }
+        let p_minus_1 = p.checked_sub(&BigUint::one()).unwrap();
+        if g < BigUint::from(2) || g >= p_minus_1 || g.modpow(&q, &p) != BigUint::one() {
+            return Err(Error::InvalidGroup);
         }
+        Ok(Group { p, g, q })
+    }
+
+    // Groupe MODP de la RFC 3526 pour une taille en bits : 1536, 2048, 3072, 4096, 6144 ou 8192
+    pub(crate) fn rfc3526(bits: u32) -> Result<Self, Error> {
+        let hex = match bits {
+            1536 => MODP_1536,
+            2048 => MODP_2048,
+            3072 => MODP_3072,
+            4096 => MODP_4096,
+            6144 => MODP_6144,
+            8192 => MODP_8192,
+            _ => return Err(Error::UnknownGroup(bits)),
+        };
+        let p = BigUint::from_hex(hex).unwrap();
+        let q = p.div_rem_small(2).0;
+        Ok(Group { p, g: BigUint::from(2), q })
+    }
+
+    // Premier sûr à partir de start, générateur le plus petit possible du sous-groupe d'ordre q
+    // (4 = 2² convient toujours, c'est un résidu quadratique différent de 1).
+    pub(crate) fn generate(start: &BigUint) -> Self {
+        let p = next_safe_prime(start);
+        let q = p.div_rem_small(2).0;
+        let g = (2..=4).map(BigUint::from)
+            .find(|h| h.modpow(&q, &p) == BigUint::one())
+            .unwrap();
+        Group { p, g, q }
+    }
+
+    // Premier sûr d'au moins `bits` bits à partir d'un point de départ aléatoire
+    pub(crate) fn generate_random(bits: usize, source: &mut impl RandomSource) -> io::Result<Self> {
+        assert!(bits >= 3, "groupe trop petit");
+        let top = BigUint::pow_small(2, bits as u32 - 1);
+        Ok(Group::generate(&(&top + &random_bits(bits - 1, source)?)))
+    }
+
+    pub(crate) fn modulus(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub(crate) fn generator(&self) -> &BigUint {
+        &self.g
+    }
+
+    // Exposant secret uniforme dans [2, q - 1]
+    pub(crate) fn private_key(&self, source: &mut impl RandomSource) -> io::Result<BigUint> {
+        let range = self.q.checked_sub(&BigUint::from(2)).unwrap();
+        Ok(&random_below(&range, source)? + &BigUint::from(2))
+    }
+
+    // Paire (clé privée, clé publique) tirée de l'entropie du système
+    pub(crate) fn key_pair(&self) -> io::Result<(BigUint, BigUint)> {
+        let private_key = self.private_key(&mut OsRandom)?;
+        let public_key = self.public_key(&private_key);
+        Ok((private_key, public_key))
+    }
+
+    pub(crate) fn public_key(&self, private_key: &BigUint) -> BigUint {
+        self.g.modpow(private_key, &self.p)
+    }
+
+    // Une clé reçue doit être dans [2, p - 2] et appartenir au sous-groupe d'ordre q,
+    // sinon le secret partagé pourrait être forcé dans un petit sous-groupe.
+    pub(crate) fn validate(&self, public_key: &BigUint) -> Result<(), Error> {
+        let p_minus_1 = self.p.checked_sub(&BigUint::one()).unwrap();
+        if *public_key < BigUint::from(2) || *public_key >= p_minus_1 { return Err(Error::OutOfRange); }
+        if public_key.modpow(&self.q, &self.p) != BigUint::one() { return Err(Error::NotInSubgroup); }
+        Ok(())
+    }
+
+    pub(crate) fn secret(&self, public_key: &BigUint, private_key: &BigUint) -> Result<BigUint, Error> {
+        self.validate(public_key)?;
+        Ok(public_key.modpow(private_key, &self.p))
     }
-    x
 }
 
-fn private_key(p: u64) -> u64 {
-    random_at_most(p)
+// cf https://en.wikipedia.org/wiki/Modular_exponentiation
+fn modular_exponentiation(base: &u64, exponent: &u64, modulus: &u64) -> u64 {
+    let result = BigUint::from(*base as u128).modpow(&BigUint::from(*exponent as u128), &BigUint::from(*modulus as u128));
+    result.to_u128().unwrap() as u64
+}
+
+// Entier uniforme dans [2, max), rejet compris dans RandomSource::below
+fn random_at_most(max: u64, source: &mut impl RandomSource) -> io::Result<u64> {
+    assert!(max > 2, "intervalle vide");
+    Ok(2 + source.below(max - 2)?)
+}
+
+fn private_key(p: u64, source: &mut impl RandomSource) -> io::Result<u64> {
+    random_at_most(p, source)
 }
 
 fn public_key(p: u64, g: u64, a: u64) -> u64 {
@@ -60,7 +301,8 @@ fn private_key_in_range_key() {
     let primes: Vec<u64> = vec![
         5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 773, 967, 3461, 6131,
     ];
-    let private_keys: Vec<u64> = primes.iter().map(|x| private_key(*x)).collect();
+    let mut source = SeededRandom::new(1);
+    let private_keys: Vec<u64> = primes.iter().map(|x| private_key(*x, &mut source).unwrap()).collect();
     for i in 0..primes.len() {
         assert!(1 < private_keys[i] && private_keys[i] < primes[i]);
     }
@@ -132,9 +374,10 @@ fn secret_key_correct_biggest_numbers() {
 #[test]
 #[cfg(feature = "big-primes")]
 fn changed_secret_key_biggest_numbers() {
-    let private_key_a = private_key(PRIME_64BIT_1);
+    let mut source = SeededRandom::new(64);
+    let private_key_a = private_key(PRIME_64BIT_1, &mut source).unwrap();
     let public_key_a = public_key(PRIME_64BIT_1, PRIME_64BIT_2, private_key_a);
-    let private_key_b = private_key(PRIME_64BIT_1);
+    let private_key_b = private_key(PRIME_64BIT_1, &mut source).unwrap();
     let public_key_b = public_key(PRIME_64BIT_1, PRIME_64BIT_2, private_key_b);
     let secret_a = secret(PRIME_64BIT_1, public_key_b, private_key_a);
     let secret_b = secret(PRIME_64BIT_1, public_key_a, private_key_b);
@@ -144,8 +387,9 @@ fn changed_secret_key_biggest_numbers() {
 fn changed_secret_key() {
     let p: u64 = 13;
     let g: u64 = 11;
-    let private_key_a = private_key(p);
-    let private_key_b = private_key(p);
+    let mut source = SeededRandom::new(13);
+    let private_key_a = private_key(p, &mut source).unwrap();
+    let private_key_b = private_key(p, &mut source).unwrap();
     let public_key_a = public_key(p, g, private_key_a);
     let public_key_b = public_key(p, g, private_key_b);
     // Key exchange
@@ -153,3 +397,131 @@ fn changed_secret_key() {
     let secret_b = secret(p, public_key_a, private_key_b);
     assert_eq!(secret_a, secret_b);
 }
+#[test]
+fn modular_exponentiation_full_range() {
+    assert_eq!(modular_exponentiation(&(u64::MAX - 1), &u64::MAX, &u64::MAX), u64::MAX - 1);
+    assert_eq!(modular_exponentiation(&2, &10, &1), 0);
+}
+#[test]
+fn rfc3526_groups() {
+    for bits in [1536, 2048, 3072, 4096, 6144, 8192] {
+        let group = Group::rfc3526(bits).unwrap();
+        assert_eq!(group.modulus().bits().len(), bits as usize);
+        assert_eq!(group.modulus().div_rem_small(1 << 32).1, 0xFFFF_FFFF);
+        assert_eq!(*group.generator(), BigUint::from(2));
+    }
+    assert_eq!(Group::rfc3526(1024).err(), Some(Error::UnknownGroup(1024)));
+}
+#[test]
+fn rfc3526_key_exchange() {
+    let group = Group::rfc3526(2048).unwrap();
+    let private_a = BigUint::pow_small(3, 150);
+    let private_b = BigUint::pow_small(7, 90);
+    let (public_a, public_b) = (group.public_key(&private_a), group.public_key(&private_b));
+    let secret_a = group.secret(&public_b, &private_a).unwrap();
+    assert_eq!(secret_a, group.secret(&public_a, &private_b).unwrap());
+    assert!(secret_a > BigUint::pow_small(2, 1800));
+}
+#[test]
+fn safe_prime_generation() {
+    let small = Group::generate(&BigUint::from(1000));
+    assert_eq!((small.modulus().to_u128(), small.generator().to_u128()), (Some(1019), Some(3)));
+    let group = Group::generate(&BigUint::from(1 << 64));
+    assert_eq!(group.modulus().to_u128(), Some(18_446_744_073_709_554_719));
+    assert_eq!(*group.generator(), BigUint::from(2));
+    let group = Group::generate(&BigUint::pow_small(10, 30));
+    assert_eq!(group.modulus().to_string(), "1000000000000000000000000001783");
+    assert_eq!(Group::generate(&BigUint::zero()).modulus().to_u128(), Some(7));
+    // q = 89, le plus grand des petits premiers du crible
+    for start in [170, 178, 179] {
+        assert_eq!(Group::generate(&BigUint::from(start)).modulus().to_u128(), Some(179));
+    }
+}
+#[test]
+fn custom_group_checks() {
+    assert!(Group::new(BigUint::from(1019), BigUint::from(3)).is_ok());
+    assert_eq!(Group::new(BigUint::from(1019), BigUint::from(2)).err(), Some(Error::InvalidGroup));
+    assert_eq!(Group::new(BigUint::from(1018), BigUint::from(3)).err(), Some(Error::InvalidGroup));
+    assert_eq!(Group::new(BigUint::from(1019), BigUint::from(1018)).err(), Some(Error::InvalidGroup));
+    // 8¹⁰ ≡ 1 mod 21 : la forme et l'ordre conviennent, mais 21 = 3 × 7
+    assert_eq!(Group::new(BigUint::from(21), BigUint::from(8)).err(), Some(Error::InvalidGroup));
+    // 13 est premier mais pas sûr : (13 - 1) / 2 = 6
+    assert_eq!(Group::new(BigUint::from(13), BigUint::from(3)).err(), Some(Error::InvalidGroup));
+}
+#[test]
+fn public_key_validation() {
+    let group = Group::new(BigUint::from(1019), BigUint::from(3)).unwrap();
+    assert_eq!(group.validate(&BigUint::one()), Err(Error::OutOfRange));
+    assert_eq!(group.validate(&BigUint::from(1018)), Err(Error::OutOfRange));
+    assert_eq!(group.validate(&BigUint::from(1019)), Err(Error::OutOfRange));
+    // 2 n'est pas un résidu quadratique modulo 1019
+    assert_eq!(group.secret(&BigUint::from(2), &BigUint::from(5)), Err(Error::NotInSubgroup));
+    let public_key = group.public_key(&BigUint::from(123));
+    assert_eq!(group.validate(&public_key), Ok(()));
+    let rfc = Group::rfc3526(1536).unwrap();
+    let p_minus_1 = rfc.modulus().checked_sub(&BigUint::one()).unwrap();
+    assert_eq!(rfc.validate(&p_minus_1), Err(Error::OutOfRange));
+    assert_eq!(rfc.validate(&BigUint::from(4)), Ok(()));
+}
+#[test]
+fn private_key_covers_the_whole_range() {
+    let mut source = SeededRandom::new(2024);
+    let mut seen = [false; 13];
+    for _ in 0..500 {
+        seen[private_key(13, &mut source).unwrap() as usize] = true;
+    }
+    assert_eq!(seen, [false, false, true, true, true, true, true, true, true, true, true, true, true]);
+    assert_eq!(random_at_most(3, &mut source).unwrap(), 2);
+}
+#[test]
+fn seeded_private_keys_are_reproducible() {
+    let keys = |seed| {
+        let mut source = SeededRandom::new(seed);
+        (0..5).map(|_| private_key(u64::MAX, &mut source).unwrap()).collect::<Vec<u64>>()
+    };
+    assert_eq!(keys(9), keys(9));
+    assert_ne!(keys(9), keys(10));
+}
+#[test]
+fn big_private_keys_in_range() {
+    let group = Group::generate(&BigUint::pow_small(10, 30));
+    let mut source = SeededRandom::new(5);
+    for _ in 0..50 {
+        let key = group.private_key(&mut source).unwrap();
+        assert!(key >= BigUint::from(2) && key < group.q);
+    }
+    let mut counts = [0; 5];
+    for _ in 0..5_000 {
+        counts[random_below(&BigUint::from(5), &mut source).unwrap().to_u128().unwrap() as usize] += 1;
+    }
+    assert_uniform(&counts, 120);
+}
+#[test]
+fn random_group_key_exchange() {
+    let mut source = SeededRandom::new(11);
+    let group = Group::generate_random(80, &mut source).unwrap();
+    assert!(group.modulus().bits().len() >= 80);
+    assert!(is_probable_prime(group.modulus()) && is_probable_prime(&group.q));
+    let (private_a, private_b) = (group.private_key(&mut source).unwrap(), group.private_key(&mut source).unwrap());
+    let (public_a, public_b) = (group.public_key(&private_a), group.public_key(&private_b));
+    assert_eq!(group.secret(&public_b, &private_a), group.secret(&public_a, &private_b));
+}
+#[test]
+fn os_key_pair() {
+    let group = Group::rfc3526(1536).unwrap();
+    let (private_key, public_key) = group.key_pair().unwrap();
+    assert!(private_key >= BigUint::from(2) && private_key < group.q);
+    assert_eq!(group.validate(&public_key), Ok(()));
+}
+#[test]
+fn entropy_errors_are_returned() {
+    struct Unavailable;
+    impl RandomSource for Unavailable {
+        fn next_u64(&mut self) -> io::Result<u64> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+    let group = Group::new(BigUint::from(1019), BigUint::from(3)).unwrap();
+    assert_eq!(group.private_key(&mut Unavailable).unwrap_err().kind(), io::ErrorKind::Unsupported);
+    assert_eq!(private_key(1019, &mut Unavailable).unwrap_err().kind(), io::ErrorKind::Unsupported);
+}
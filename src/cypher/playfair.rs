@@ -0,0 +1,95 @@
+// https://en.wikipedia.org/wiki/Playfair_cipher
+//
+// Le texte est chiffré par paires de lettres dans un carré latin à mot-clé :
+// même ligne, on prend la lettre à droite ; même colonne, celle du dessous ;
+// sinon les deux lettres échangent leurs colonnes (coins opposés du rectangle).
+// Une paire de lettres identiques est séparée par un x, et un x complète la dernière paire.
+
+use crate::cypher::{polybius::Square, Cipher};
+
+pub(crate) struct Playfair {
+    square: Square,
+}
+
+impl Playfair {
+    pub(crate) fn new(keyword: &str) -> Self {
+        Playfair { square: Square::latin(keyword) }
+    }
+
+    // q sépare ou complète un x, pour ne jamais former de paire identique
+    fn filler(&self, (row, column): (usize, usize)) -> (usize, usize) {
+        let x = self.square.position('x').unwrap();
+        if (row, column) == x { self.square.position('q').unwrap() } else { x }
+    }
+
+    fn pairs(&self, text: &str) -> Vec<[(usize, usize); 2]> {
+        let letters = text.chars().filter_map(|c| self.square.position(c)).collect::<Vec<(usize, usize)>>();
+        let mut pairs = Vec::with_capacity(letters.len() / 2 + 1);
+        let mut i = 0;
+        while i < letters.len() {
+            match letters.get(i + 1) {
+                Some(next) if *next != letters[i] => {
+                    pairs.push([letters[i], *next]);
+                    i += 2;
+                },
+                _ => {
+                    pairs.push([letters[i], self.filler(letters[i])]);
+                    i += 1;
+                },
+            }
+        }
+        pairs
+    }
+
+    // shift vaut 1 au chiffrement, size - 1 au déchiffrement
+    fn transform(&self, text: &str, shift: usize) -> String {
+        let mut output = String::with_capacity(text.len());
+        for [(r1, c1), (r2, c2)] in self.pairs(text) {
+            let (a, b) = if r1 == r2 {
+                ((r1, c1 + shift), (r2, c2 + shift))
+            } else if c1 == c2 {
+                ((r1 + shift, c1), (r2 + shift, c2))
+            } else {
+                ((r1, c2), (r2, c1))
+            };
+            output.push(self.square.at(a.0, a.1));
+            output.push(self.square.at(b.0, b.1));
+        }
+        output
+    }
+}
+
+// Le déchiffrement ne retire pas les x de remplissage, indiscernables d'un x du clair
+impl Cipher for Playfair {
+    fn encrypt(&self, plain: &str) -> String {
+        self.transform(plain, 1)
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        self.transform(cipher, self.square.size() - 1)
+    }
+}
+
+#[test]
+fn playfair_wikipedia() {
+    let cipher = Playfair::new("playfair example");
+    assert_eq!(cipher.encrypt("Hide the gold in the tree stump"), "bmodzbxdnabekudmuixmmouvif");
+    assert_eq!(cipher.decrypt("bmodzbxdnabekudmuixmmouvif"), "hidethegoldinthetrexestump");
+}
+#[test]
+fn playfair_grouped() {
+    let cipher = crate::cypher::Grouped::new(Playfair::new("playfair example"), crate::cypher::Grouping::Blocks(2));
+    assert_eq!(cipher.encrypt("Hide the gold"), "bm od zb xd na ge");
+    assert_eq!(cipher.decrypt("bm od zb xd na ge"), "hidethegoldx");
+}
+#[test]
+fn playfair_doubled_x_uses_q() {
+    let cipher = Playfair::new("monarchy");
+    let encrypted = cipher.encrypt("xx");
+    assert_eq!(cipher.decrypt(&encrypted), "xqxq");
+}
+#[test]
+fn playfair_j_is_i() {
+    let cipher = Playfair::new("jazz");
+    assert_eq!(cipher.encrypt("jump"), cipher.encrypt("iump"));
+}
@@ -0,0 +1,176 @@
+// https://en.wikipedia.org/wiki/Polybius_square
+// https://en.wikipedia.org/wiki/ADFGVX_cipher
+//
+// Chaque lettre est remplacée par les étiquettes de sa ligne et de sa colonne dans un carré
+// construit à partir d'un alphabet à mot-clé. ADFGVX fait ensuite suivre d'une transposition
+// en colonnes, ce qui sépare les deux coordonnées d'une même lettre.
+
+use crate::cypher::{alphabet::{Alphabet, Case, Unknown}, transposition::Columnar, Cipher, PLAIN};
+
+// Alphabet latin sans le j, confondu avec le i pour tenir dans un carré de 5 × 5
+const LATIN_SQUARE: &str = "abcdefghiklmnopqrstuvwxyz";
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Error {
+    NotSquare(usize),
+    InvalidLabels,
+    EmptyKey,
+}
+
+// Carré à mot-clé : le rang r de l'alphabet à mot-clé est en ligne r / size, colonne r % size
+pub(crate) struct Square {
+    alphabet: Alphabet,
+    size: usize,
+    // Symbole absent du carré et remplacé par un autre (j par i dans le carré latin)
+    merged: Option<(char, char)>,
+}
+
+impl Square {
+    pub(crate) fn new(keyword: &str, alphabet: Alphabet) -> Result<Self, Error> {
+        let size = alphabet.len().isqrt();
+        if size * size != alphabet.len() { return Err(Error::NotSquare(alphabet.len())); }
+        Ok(Square { alphabet: alphabet.keyed(keyword), size, merged: None })
+    }
+
+    // Carré classique de 25 lettres où j est chiffré comme i
+    pub(crate) fn latin(keyword: &str) -> Self {
+        let alphabet = Alphabet::new(LATIN_SQUARE, Case::Fold, Unknown::Drop).unwrap();
+        let keyword = keyword.to_lowercase().replace('j', "i");
+        Square { merged: Some(('j', 'i')), ..Square::new(&keyword, alphabet).unwrap() }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn position(&self, c: char) -> Option<(usize, usize)> {
+        let c = match self.merged {
+            Some((from, to)) if c.to_lowercase().eq([from]) => to,
+            _ => c,
+        };
+        self.alphabet.rank(c).map(|r| (r / self.size, r % self.size))
+    }
+
+    // Les coordonnées sont prises modulo la taille du carré
+    pub(crate) fn at(&self, row: usize, column: usize) -> char {
+        self.alphabet.symbol(row % self.size * self.size + column % self.size)
+    }
+}
+
+pub(crate) struct Polybius {
+    square: Square,
+    labels: Vec<char>,
+}
+
+impl Polybius {
+    // Une étiquette distincte par ligne (et par colonne)
+    pub(crate) fn new(square: Square, labels: &str) -> Result<Self, Error> {
+        let labels = labels.chars().collect::<Vec<char>>();
+        let distinct = labels.iter().enumerate().all(|(i, l)| !labels[..i].iter().any(|m| m.eq_ignore_ascii_case(l)));
+        if labels.len() != square.size() || !distinct { return Err(Error::InvalidLabels); }
+        Ok(Polybius { square, labels })
+    }
+
+    // Carré latin numéroté de 1 à 5
+    pub(crate) fn latin(keyword: &str) -> Self {
+        Polybius::new(Square::latin(keyword), "12345").unwrap()
+    }
+
+    fn label(&self, c: char) -> Option<usize> {
+        self.labels.iter().position(|l| l.eq_ignore_ascii_case(&c))
+    }
+}
+
+// Les caractères absents du carré sont supprimés
+impl Cipher for Polybius {
+    fn encrypt(&self, plain: &str) -> String {
+        plain.chars()
+            .filter_map(|c| self.square.position(c))
+            .flat_map(|(row, column)| [self.labels[row], self.labels[column]])
+            .collect()
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        cipher.chars()
+            .filter_map(|c| self.label(c))
+            .collect::<Vec<usize>>()
+            .chunks_exact(2)
+            .map(|pair| self.square.at(pair[0], pair[1]))
+            .collect()
+    }
+}
+
+pub(crate) struct Adfgvx {
+    polybius: Polybius,
+    transposition: Columnar,
+}
+
+impl Adfgvx {
+    // Carré de 6 × 6 : lettres et chiffres
+    pub(crate) fn new(square_keyword: &str, transposition_key: &str) -> Result<Self, Error> {
+        let alphabet = Alphabet::new(&format!("{PLAIN}0123456789"), Case::Fold, Unknown::Drop).unwrap();
+        Adfgvx::with_square(Square::new(square_keyword, alphabet)?, "ADFGVX", transposition_key)
+    }
+
+    // Version antérieure ADFGX, sur le carré latin de 5 × 5
+    pub(crate) fn adfgx(square_keyword: &str, transposition_key: &str) -> Result<Self, Error> {
+        Adfgvx::with_square(Square::latin(square_keyword), "ADFGX", transposition_key)
+    }
+
+    fn with_square(square: Square, labels: &str, transposition_key: &str) -> Result<Self, Error> {
+        Ok(Adfgvx {
+            polybius: Polybius::new(square, labels)?,
+            transposition: Columnar::new(transposition_key).map_err(|_| Error::EmptyKey)?,
+        })
+    }
+}
+
+impl Cipher for Adfgvx {
+    fn encrypt(&self, plain: &str) -> String {
+        self.transposition.encrypt(&self.polybius.encrypt(plain))
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        let labels = cipher.chars().filter(|c| self.polybius.label(*c).is_some()).collect::<String>();
+        self.polybius.decrypt(&self.transposition.decrypt(&labels))
+    }
+}
+
+#[test]
+fn polybius_hello() {
+    let cipher = Polybius::latin("");
+    assert_eq!(cipher.encrypt("Hello, world"), "23153131345234423114");
+    assert_eq!(cipher.decrypt("23153131345234423114"), "helloworld");
+}
+#[test]
+fn polybius_merges_i_and_j() {
+    let cipher = Polybius::latin("");
+    assert_eq!(cipher.encrypt("Jim"), cipher.encrypt("iim"));
+    assert_eq!(cipher.decrypt(&cipher.encrypt("jim")), "iim");
+}
+#[test]
+fn polybius_grouped_by_pairs() {
+    let cipher = crate::cypher::Grouped::new(Polybius::latin("zebras"), crate::cypher::Grouping::Blocks(2));
+    assert_eq!(cipher.encrypt("zebra"), "11 12 13 14 15");
+    assert_eq!(cipher.decrypt("11 12 13 14 15"), "zebra");
+}
+#[test]
+fn adfgvx_wikipedia() {
+    let cipher = Adfgvx::new("na1c3h8tb2ome5wrpd4f6g7i9j0klqsuvxyz", "PRIVACY").unwrap();
+    assert_eq!(cipher.encrypt("ATTACK AT 1200AM"), "DGDDDAGDDGAFADDFDADVDVFAADVX");
+    assert_eq!(cipher.decrypt("DGDD DAGD DGAF ADDF DADV DVFA ADVX"), "attackat1200am");
+}
+#[test]
+fn adfgx_round_trip() {
+    let cipher = Adfgvx::adfgx("playfair example", "cargo").unwrap();
+    let encrypted = cipher.encrypt("Attack at once");
+    assert!(encrypted.chars().all(|c| "ADFGX".contains(c)));
+    assert_eq!(cipher.decrypt(&encrypted), "attackatonce");
+}
+#[test]
+fn square_errors() {
+    assert!(matches!(Square::new("", Alphabet::latin()), Err(Error::NotSquare(26))));
+    assert!(matches!(Polybius::new(Square::latin(""), "1234"), Err(Error::InvalidLabels)));
+    assert!(matches!(Polybius::new(Square::latin(""), "aBcdA"), Err(Error::InvalidLabels)));
+    assert!(matches!(Adfgvx::new("", ""), Err(Error::EmptyKey)));
+}
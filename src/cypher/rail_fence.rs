@@ -1,3 +1,7 @@
+use crate::cypher::Cipher;
+#[cfg(test)]
+use crate::cypher::random::pseudo_random_texts;
+
 /*
    Forme des rails (Ex. rang 6 avec intervalle de 10) :
 ⇩ Point de départ, on compte le nombre d'éléments entre 2 points de même intervalle.
@@ -8,54 +12,24 @@ _         _         _
     _ _       _ _       X
      _         _         X
 */
-type Diagonal = usize;
-trait ZigZag {
-    fn is_zag(&self) -> bool;
-    fn is_zig(&self) -> bool;
-}
-
-impl ZigZag for Diagonal {
-    #[inline]
-    fn is_zag(&self) -> bool {
-        *self & 1 == 0
-    }
-
-    #[inline]
-    fn is_zig(&self) -> bool {
-        !self.is_zag()
-    }
-}
-
-#[test]
-fn cypher_zag() {
-    let mut n: Diagonal = 2;
-    assert!(n.is_zag());
-    n *= 2;
-    assert!(n.is_zag());
-    n *= 9;
-    assert!(n.is_zag());
-}
-
-#[test]
-fn cypher_zig() {
-    let mut n: Diagonal = 3;
-    assert!(!n.is_zag());
-    n *= 3;
-    assert!(!n.is_zag());
-    n *= 9;
-    assert!(!n.is_zag());
-}
 
 // https://en.wikipedia.org/wiki/Rail_fence_cipher
-struct RailFence {
-    count: u32
+pub(crate) struct RailFence {
+    count: u32,
+    // Position de départ dans le zigzag : 0 commence sur le rail de tête en descendant,
+    // count - 1 sur le rail de queue, au-delà on remonte.
+    offset: usize,
 }
 
 impl RailFence {
-    fn new(rails: u32) -> Self {
-        Self {
-            count: rails
-        }
+    pub(crate) fn new(rails: u32) -> Self {
+        RailFence::with_offset(rails, 0)
+    }
+
+    pub(crate) fn with_offset(rails: u32, offset: usize) -> Self {
+        let mut rail_fence = Self { count: rails, offset: 0 };
+        rail_fence.offset = offset % rail_fence.interval().max(1);
+        rail_fence
     }
 
     fn count(&self) -> usize {
@@ -64,127 +38,67 @@ impl RailFence {
 
     // distance maximale entre 2 points d'un même rail (zig - > zig ou zag -> zag)
     fn interval(&self) -> usize {
-        2 * (self.count() - 1)
+        2 * (self.count().max(1) - 1)
     }
 
-    // Pour déterminer les "pas" entre chaque lettres d' un rail donné en fonction du nombre de rail.
-    // il y a une symmétrie des pas étant donné la nature du triangle.
-    // Ex : pour un rail de 4 : (en code - 1 pour index base 0)
-    // rail tête : 6-6-6 ...
-    // rail intermédiaire 1 : 4-2-4-2 ... Intermédiaires on a (zig -> zag, zag -> zig )
-    // rail intermédiaire 2 : 2-4-2-4 ...
-    // rail de queue : 6-6-6 ...
-    //
-    // Pour utiliser les pas, il suffit d'inclure ce vecteur en l'inversant dans un zip de ce même vecteur (cf. encode)
-    fn steps(&self) -> Vec<u32> {
-        let limit = self.interval() as u32;
-        let mut steps = (2..=limit).rev().step_by(2).collect::<Vec<u32>>();
-        steps.push(limit);
-
-        steps
+    // Rail de la i-ème lettre : descente sur les count premières positions de la période,
+    // remontée ensuite (cf. schéma ci-dessus)
+    fn rail_of(&self, i: usize) -> usize {
+        let interval = self.interval();
+        if interval == 0 { return 0; }
+        match (i + self.offset) % interval {
+            t if t < self.count() => t,
+            t => interval - t,
+        }
     }
 
     fn encode(&self, text: &str) -> String {
-        let steps = self.steps();
-        steps.iter().zip(steps.iter().rev()).enumerate().map(|(idx, (zig, zag))| {
-            let mut iter = text.chars().skip(idx);
-            let mut string = String::new();
-            string.push(iter.next().unwrap());
-            loop {
-                // Il est nécessaire de décrémenter car le next précédent et nth décale la position courante.
-                match (iter.nth((*zig - 1) as usize), iter.nth((*zag - 1) as usize)) {
-                    (Some(a), Some(b)) => { string.push(a); string.push(b); },
-                    (None, Some(b)) => string.push(b),
-                    (Some(a), None) => string.push(a),
-                    _ => break
-                }
-            }
-        string
-        }).collect::<String>()
+        let mut rails = vec![String::new(); self.count().max(1)];
+        for (i, c) in text.chars().enumerate() {
+            rails[self.rail_of(i)].push(c);
+        }
+        rails.concat()
     }
 
     fn decode(&self, cipher: &str) -> String {
-        let rails = self.rails(cipher);
-        let steps = self.steps();
-        let mut string: Vec<char> = Vec::new();
-        string.push(' ');
-        string = string.repeat(cipher.len());
-        _ = rails.iter().zip(steps.iter().zip(steps.iter().rev()))
-            .enumerate().map(|(pos,(str, (zig, zag)))| {
-            let mut iter = str.chars();
-            let mut position = pos;
-            string[position] = iter.next().unwrap();
-            loop {
-                match (iter.next(), iter.next()) {
-                    (Some(a), Some(b)) => {
-                        position += *zig as usize;
-                        string[position] = a;
-                        position += *zag as usize;
-                        string[position] = b;
-                    },
-                    (None, Some(b)) => {
-                        position += *zag as usize;
-                        string[position] = b;
-                    },
-                    (Some(a), None) => {
-                        position += *zig as usize;
-                        string[position] = a;
-                    },
-                    _ => break
-                }
-            }
-            ""
-        }).collect::<String>();
-
-        string.iter().collect::<String>()
+        let mut rails = self.rails(cipher).into_iter().map(|rail| rail.chars()).collect::<Vec<_>>();
+        (0..cipher.chars().count())
+            .map(|i| rails[self.rail_of(i)].next().unwrap())
+            .collect()
     }
 
     // Découper la chaîne cryptée selon le nombre de rail.
-    //
-    // L + y / (N + x(N - 1)) pour découvrir les lettres manquantes
-    // L = longueur du texte
-    // N = nombre de rail
-    // y = nombre de lettre manquante
-    // x = nombre de diagonale complète
-    //
-    // K = L / 2(N - 1)
-    // si les rails sont équilibrés alors rail de tête et queue sont égale à K
-    // et rails intermédiaires de 2K. Sinon déterminer si rail incomplet zig ou zag.
+    // Le nombre de lettres de chaque rail est obtenu en suivant le zigzag sur toute la longueur,
+    // puis les coupures se font aux frontières de caractères (et non d'octets).
     fn rails<'a>(&self, cipher: &'a str) -> Vec<&'a str> {
-        let intervalle = self.interval();
-        // Calcul du nombre de lettre pour chaque rail si équilibré
-        let period_k = cipher.len() / intervalle;
-        let mut lettre_par_rail = Vec::<usize>::with_capacity(self.count());
-        lettre_par_rail.push(period_k); // 1er
-        for _ in 0..self.count - 2 {
-            lettre_par_rail.push(2 * period_k); // intermed
+        let mut lettre_par_rail = vec![0; self.count().max(1)];
+        for i in 0..cipher.chars().count() {
+            lettre_par_rail[self.rail_of(i)] += 1;
         }
-        lettre_par_rail.push(period_k); // dernier
 
-        let y = cipher.len() % intervalle;
-        if y != 0 { // la diagonale finale est incomplète, vérification et correction éventuel de la longueur d'un rail
-            let l = cipher.len() - self.count();
-            let n_1 = self.count() - 1;
-            let x: Diagonal = l / n_1 + 1; // + 1 la diagonale incomplète suite à l'entrée du if
-            let (mut index, inc) : (i32, i32) = if x.is_zag() { (0, 1) } else { (lettre_par_rail.len() as i32 - 1, -1) };
-            for _ in 0..y {
-                lettre_par_rail[index as usize] += 1;
-                index += inc;
-            }
-        }
-        // Découpage des rails de n longueur selon leur place et correction (cf. period_k)
         let mut rails: Vec<&str> = Vec::new();
-        let mut splits: (&str, &str) = ("", cipher);
+        let mut reste = cipher;
         for rail_lng in lettre_par_rail {
-            splits = splits.1.split_at(rail_lng);
-            rails.push(splits.0);
+            let coupure = reste.char_indices().nth(rail_lng).map_or(reste.len(), |(i, _)| i);
+            let (rail, suite) = reste.split_at(coupure);
+            rails.push(rail);
+            reste = suite;
         }
 
-        // Si préférence d'utilisation avec pop au lieu d'iter, faut inverser le vecteur avant
         rails
     }
 }
 
+impl Cipher for RailFence {
+    fn encrypt(&self, plain: &str) -> String {
+        self.encode(plain)
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        self.decode(cipher)
+    }
+}
+
 #[test]
 fn encode_with_two_rails() {
     let input = "XOXOXOXOXOXOXOXOXO";
@@ -247,4 +161,48 @@ fn encode_wide_characters() {
     let output = rail_fence.encode(input);
     let expected = "古びの池飛込水音蛙む";
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+#[test]
+fn rail_fence_as_cipher() {
+    let cipher: Box<dyn Cipher> = Box::new(RailFence::new(3));
+    let encrypted = cipher.encrypt("WEAREDISCOVEREDFLEEATONCE");
+    assert_eq!(encrypted, "WECRLTEERDSOEEFEAOCAIVDEN");
+    assert_eq!(cipher.decrypt(&encrypted), "WEAREDISCOVEREDFLEEATONCE");
+}
+#[test]
+fn decode_every_partial_period() {
+    let text = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    for rails in 2..=8 {
+        let rail_fence = RailFence::new(rails);
+        for len in rails as usize..=text.len() {
+            assert_eq!(rail_fence.decode(&rail_fence.encode(&text[..len])), &text[..len]);
+        }
+    }
+}
+#[test]
+fn encode_with_offset() {
+    // Départ sur le 2e rail en descendant
+    let rail_fence = RailFence::with_offset(3, 1);
+    assert_eq!(rail_fence.encode("WEAREDISCOVEREDFLEEATONCE"), "RSEFACWAEICVRDLETNEEDOEEO");
+    assert_eq!(rail_fence.decode("RSEFACWAEICVRDLETNEEDOEEO"), "WEAREDISCOVEREDFLEEATONCE");
+    assert_eq!(RailFence::with_offset(3, 4).encode("ABCDE"), RailFence::new(3).encode("ABCDE"));
+}
+#[test]
+fn decode_wide_characters() {
+    let rail_fence = RailFence::new(3);
+    assert_eq!(rail_fence.rails("古びの池飛込水音蛙む"), vec!["古びの", "池飛込水音", "蛙む"]);
+    assert_eq!(rail_fence.decode("古びの池飛込水音蛙む"), "古池蛙飛び込む水の音");
+}
+#[test]
+fn round_trip_property() {
+    for text in pseudo_random_texts(17, 80) {
+        for rails in 1..=9 {
+            for offset in 0..2 * rails as usize {
+                let rail_fence = RailFence::with_offset(rails, offset);
+                let encoded = rail_fence.encode(&text);
+                assert_eq!(encoded.chars().count(), text.chars().count());
+                assert_eq!(rail_fence.decode(&encoded), text);
+            }
+        }
+    }
+}
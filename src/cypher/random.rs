@@ -0,0 +1,126 @@
+// Sources d'aléa pour la génération de clés : l'entropie du système en usage normal,
+// un générateur déterministe à graine pour rendre les tests reproductibles.
+
+use std::io;
+
+// Une source qui échoue (entropie indisponible) le signale : jamais de valeur prévisible à la place
+pub(crate) trait RandomSource {
+    fn next_u64(&mut self) -> io::Result<u64>;
+
+    // Entier uniforme dans [0, bound) par rejet : on écarte le dernier intervalle incomplet
+    // de [0, 2^64) pour que chaque reste modulo bound ait le même nombre d'antécédents.
+    fn below(&mut self, bound: u64) -> io::Result<u64> {
+        assert!(bound > 0, "intervalle vide");
+        let rejected = (u64::MAX - bound + 1) % bound;
+        loop {
+            let x = self.next_u64()?;
+            if x <= u64::MAX - rejected { return Ok(x % bound); }
+        }
+    }
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn next_u64(&mut self) -> io::Result<u64> {
+        (**self).next_u64()
+    }
+}
+
+// Entropie du système d'exploitation via getrandom (getrandom(2), BCryptGenRandom, random_get…).
+// Sur une cible sans source connue, chaque tirage renvoie une erreur Unsupported.
+pub(crate) struct OsRandom;
+
+impl RandomSource for OsRandom {
+    #[cfg(any(unix, windows, target_os = "wasi"))]
+    fn next_u64(&mut self) -> io::Result<u64> {
+        let mut bytes = [0; 8];
+        getrandom::getrandom(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    #[cfg(not(any(unix, windows, target_os = "wasi")))]
+    fn next_u64(&mut self) -> io::Result<u64> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "aucune source d'entropie sur cette cible"))
+    }
+}
+
+// https://en.wikipedia.org/wiki/Xorshift#splitmix64
+// Déterministe et sans aucune valeur cryptographique : réservé aux tests
+pub(crate) struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    pub(crate) fn new(seed: u64) -> Self {
+        SeededRandom { state: seed }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u64(&mut self) -> io::Result<u64> {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Ok(z ^ (z >> 31))
+    }
+}
+
+// Suite de valeurs imposée, pour vérifier le rejet
+#[cfg(test)]
+struct Scripted(Vec<u64>);
+
+#[cfg(test)]
+impl RandomSource for Scripted {
+    fn next_u64(&mut self) -> io::Result<u64> {
+        Ok(self.0.remove(0))
+    }
+}
+
+// Textes de longueur 0 à count - 1 mêlant ASCII, accents, idéogrammes et emoji,
+// pour les propriétés d'aller-retour des chiffrements
+#[cfg(test)]
+pub(crate) fn pseudo_random_texts(seed: u64, count: usize) -> Vec<String> {
+    let symbols = ['a', 'Z', ' ', 'é', 'ß', '古', '池', '🦀', '9', 'ç'];
+    let mut source = SeededRandom::new(seed);
+    (0..count)
+        .map(|len| (0..len).map(|_| symbols[source.below(symbols.len() as u64).unwrap() as usize]).collect())
+        .collect()
+}
+
+// Chaque case doit s'écarter de moins de `tolerance` de la moyenne des tirages
+#[cfg(test)]
+pub(crate) fn assert_uniform(counts: &[u32], tolerance: u32) {
+    let expected = counts.iter().sum::<u32>() / counts.len() as u32;
+    assert!(counts.iter().all(|c| c.abs_diff(expected) < tolerance), "{counts:?}");
+}
+
+#[test]
+fn seeded_is_reproducible() {
+    let (mut a, mut b) = (SeededRandom::new(42), SeededRandom::new(42));
+    let first = (0..10).map(|_| a.next_u64().unwrap()).collect::<Vec<u64>>();
+    assert_eq!(first, (0..10).map(|_| b.next_u64().unwrap()).collect::<Vec<u64>>());
+    assert_ne!(first, (0..10).map(|_| SeededRandom::new(43).next_u64().unwrap()).collect::<Vec<u64>>());
+    assert_eq!(SeededRandom::new(0).next_u64().unwrap(), 0xE220_A839_7B1D_CDAF);
+}
+#[test]
+fn os_random_varies() {
+    let values = (0..4).map(|_| OsRandom.next_u64().unwrap()).collect::<Vec<u64>>();
+    assert!(values.windows(2).any(|w| w[0] != w[1]));
+}
+#[test]
+fn below_rejects_the_incomplete_interval() {
+    let bound = (1 << 63) + 1;
+    assert_eq!(Scripted(vec![u64::MAX, 1 << 63]).below(bound).unwrap(), 1 << 63);
+    assert_eq!(Scripted(vec![u64::MAX, u64::MAX - 1, 5]).below(bound).unwrap(), 5);
+    assert_eq!(Scripted(vec![u64::MAX]).below(1 << 32).unwrap(), (1 << 32) - 1);
+    assert_eq!(Scripted(vec![u64::MAX]).below(1).unwrap(), 0);
+}
+#[test]
+fn below_is_uniform() {
+    let mut source = SeededRandom::new(7);
+    let mut counts = [0u32; 6];
+    for _ in 0..60_000 {
+        counts[source.below(6).unwrap() as usize] += 1;
+    }
+    assert_uniform(&counts, 400);
+}
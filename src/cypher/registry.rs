@@ -0,0 +1,177 @@
+// Sélection d'un chiffrement par son nom, la clé étant fournie sous forme textuelle
+// (ex. "affine" avec "5,7", "rot" avec "13", "rail_fence" avec "3" ou "3,1" pour un décalage,
+// "double_transposition" avec "zebras,stripe", "route" avec le nombre de colonnes suivi
+// éventuellement de ",spiral" ou ",boustrophedon", "adfgvx" avec le mot-clé du carré puis celui
+// de la transposition).
+
+use super::{affine::Affine, alphabet::Alphabet, atbase::Substitution, playfair::Playfair, polybius::{Adfgvx, Polybius}, rail_fence::RailFence, rotational::Rotation, transposition::{Columnar, DoubleTransposition, Route, RouteCipher}, vigenere::{Kind, Vigenere}, Cipher, Grouped, Grouping};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    UnknownCipher(String),
+    InvalidKey(String),
+}
+
+type Constructor = fn(&str) -> Result<Box<dyn Cipher>, Error>;
+
+const REGISTRY: [(&str, Constructor); 15] = [
+    ("atbash", atbash),
+    ("affine", affine),
+    ("rot", rot),
+    ("rail_fence", rail_fence),
+    ("vigenere", |key| polyalphabetic(key, Kind::Vigenere)),
+    ("beaufort", |key| polyalphabetic(key, Kind::Beaufort)),
+    ("variant_beaufort", |key| polyalphabetic(key, Kind::VariantBeaufort)),
+    ("autokey", autokey),
+    ("columnar", columnar),
+    ("double_transposition", double_transposition),
+    ("route", route),
+    ("keyword", |key| Ok(Box::new(Substitution::keyword(key, Alphabet::latin_preserving())))),
+    ("playfair", |key| Ok(Box::new(Playfair::new(key)))),
+    ("polybius", |key| Ok(Box::new(Polybius::latin(key)))),
+    ("adfgvx", adfgvx),
+];
+
+fn invalid(key: &str) -> Error {
+    Error::InvalidKey(key.to_string())
+}
+
+fn atbash(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    if !key.is_empty() { return Err(invalid(key)); }
+    Ok(Box::new(Substitution::atbash(Alphabet::latin())))
+}
+
+fn affine(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (a, b) = key.split_once(',').ok_or_else(|| invalid(key))?;
+    let (a, b) = (a.trim().parse().map_err(|_| invalid(key))?, b.trim().parse().map_err(|_| invalid(key))?);
+    Ok(Box::new(Affine::new(a, b, Alphabet::latin()).map_err(|_| invalid(key))?))
+}
+
+fn rot(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    Ok(Box::new(Rotation::new(key.trim().parse().map_err(|_| invalid(key))?, Alphabet::latin_preserving())))
+}
+
+fn rail_fence(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (rails, offset) = key.split_once(',').unwrap_or((key, "0"));
+    match (rails.trim().parse::<u32>(), offset.trim().parse::<usize>()) {
+        (Ok(rails), Ok(offset)) if rails >= 2 => Ok(Box::new(RailFence::with_offset(rails, offset))),
+        _ => Err(invalid(key)),
+    }
+}
+
+fn polyalphabetic(key: &str, kind: Kind) -> Result<Box<dyn Cipher>, Error> {
+    Ok(Box::new(Vigenere::new(key, kind, Alphabet::latin_preserving()).map_err(|_| invalid(key))?))
+}
+
+fn autokey(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    Ok(Box::new(Vigenere::autokey(key, Alphabet::latin_preserving()).map_err(|_| invalid(key))?))
+}
+
+fn columnar(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    Ok(Box::new(Columnar::new(key.trim()).map_err(|_| invalid(key))?))
+}
+
+fn double_transposition(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (first, second) = key.split_once(',').ok_or_else(|| invalid(key))?;
+    Ok(Box::new(DoubleTransposition::new(first.trim(), second.trim()).map_err(|_| invalid(key))?))
+}
+
+// Parcours en spirale par défaut
+fn route(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (columns, route) = key.split_once(',').unwrap_or((key, "spiral"));
+    let route = match route.trim().to_lowercase().as_str() {
+        "spiral" => Route::Spiral,
+        "boustrophedon" => Route::Boustrophedon,
+        _ => return Err(invalid(key)),
+    };
+    let columns = columns.trim().parse().map_err(|_| invalid(key))?;
+    Ok(Box::new(RouteCipher::new(columns, route).map_err(|_| invalid(key))?))
+}
+
+fn adfgvx(key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (square, transposition) = key.split_once(',').ok_or_else(|| invalid(key))?;
+    Ok(Box::new(Adfgvx::new(square.trim(), transposition.trim()).map_err(|_| invalid(key))?))
+}
+
+fn names() -> impl Iterator<Item = &'static str> {
+    REGISTRY.iter().map(|(name, _)| *name)
+}
+
+// Le nom est insensible à la casse
+fn by_name(name: &str, key: &str) -> Result<Box<dyn Cipher>, Error> {
+    let (_, constructor) = REGISTRY.iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .ok_or_else(|| Error::UnknownCipher(name.to_string()))?;
+    constructor(key)
+}
+
+fn grouped_by_name(name: &str, key: &str, grouping: Grouping) -> Result<Grouped<Box<dyn Cipher>>, Error> {
+    Ok(Grouped::new(by_name(name, key)?, grouping))
+}
+
+#[test]
+fn registry_names() {
+    assert_eq!(names().collect::<Vec<&str>>(), ["atbash", "affine", "rot", "rail_fence", "vigenere", "beaufort", "variant_beaufort", "autokey", "columnar", "double_transposition",
+        "route", "keyword", "playfair", "polybius", "adfgvx"]);
+}
+#[test]
+fn pick_cipher_by_name() {
+    let atbash = by_name("Atbash", "").unwrap();
+    assert_eq!(atbash.encrypt("yes"), "bvh");
+    let affine = by_name("affine", "5, 7").unwrap();
+    assert_eq!(affine.encrypt("yes"), "xbt");
+    assert_eq!(affine.decrypt("xbt"), "yes");
+    let rot = by_name("rot", "13").unwrap();
+    assert_eq!(rot.encrypt("Hello"), "Uryyb");
+    let rail = by_name("rail_fence", "3").unwrap();
+    assert_eq!(rail.decrypt("WECRLTEERDSOEEFEAOCAIVDEN"), "WEAREDISCOVEREDFLEEATONCE");
+    let vigenere = by_name("vigenere", "lemon").unwrap();
+    assert_eq!(vigenere.encrypt("Attack at dawn!"), "Lxfopv ef rnhr!");
+    let autokey = by_name("autokey", "queenly").unwrap();
+    assert_eq!(autokey.decrypt("qnxepvytwtwp"), "attackatdawn");
+    let offset_rail = by_name("rail_fence", "3,1").unwrap();
+    assert_eq!(offset_rail.encrypt("WEAREDISCOVEREDFLEEATONCE"), "RSEFACWAEICVRDLETNEEDOEEO");
+    let columnar = by_name("columnar", "zebras").unwrap();
+    assert_eq!(columnar.encrypt("WEAREDISCOVEREDFLEEATONCE"), "EVLNACDTESEAROFODEECWIREE");
+    let double = by_name("double_transposition", "zebras, stripe").unwrap();
+    assert_eq!(double.decrypt("CAEENSOIAEDRLEFWEDREEVTOC"), "WEAREDISCOVEREDFLEEATONCE");
+    let spiral = by_name("route", "5").unwrap();
+    assert_eq!(spiral.encrypt("WEAREDISCOVEREDFLEEATONCE"), "WEAREODAECNOTFVDISCEEELER");
+    let boustrophedon = by_name("route", "3, Boustrophedon").unwrap();
+    assert_eq!(boustrophedon.decrypt("adghebcf"), "abcdefgh");
+    let keyword = by_name("keyword", "kryptos").unwrap();
+    assert_eq!(keyword.encrypt("Knowledge is power"), "Dghvetpst bm ihvtl");
+    let playfair = by_name("playfair", "playfair example").unwrap();
+    assert_eq!(playfair.encrypt("Hide the gold"), "bmodzbxdnage");
+    let polybius = by_name("polybius", "").unwrap();
+    assert_eq!(polybius.decrypt("2315313134"), "hello");
+    let adfgvx = by_name("adfgvx", "na1c3h8tb2ome5wrpd4f6g7i9j0klqsuvxyz,privacy").unwrap();
+    assert_eq!(adfgvx.encrypt("attack at 1200am"), "DGDDDAGDDGAFADDFDADVDVFAADVX");
+}
+#[test]
+fn registry_errors() {
+    assert_eq!(by_name("enigma", "").err(), Some(Error::UnknownCipher("enigma".to_string())));
+    assert_eq!(by_name("affine", "13,5").err(), Some(Error::InvalidKey("13,5".to_string())));
+    assert_eq!(by_name("affine", "5").err(), Some(Error::InvalidKey("5".to_string())));
+    assert_eq!(by_name("rot", "x").err(), Some(Error::InvalidKey("x".to_string())));
+    assert_eq!(by_name("rail_fence", "1").err(), Some(Error::InvalidKey("1".to_string())));
+    assert_eq!(by_name("rail_fence", "3,x").err(), Some(Error::InvalidKey("3,x".to_string())));
+    assert_eq!(by_name("double_transposition", "zebras").err(), Some(Error::InvalidKey("zebras".to_string())));
+    assert_eq!(by_name("route", "0").err(), Some(Error::InvalidKey("0".to_string())));
+    assert_eq!(by_name("route", "4,zigzag").err(), Some(Error::InvalidKey("4,zigzag".to_string())));
+    assert_eq!(by_name("adfgvx", "key").err(), Some(Error::InvalidKey("key".to_string())));
+    assert_eq!(by_name("atbash", "key").err(), Some(Error::InvalidKey("key".to_string())));
+}
+#[test]
+fn configurable_grouping() {
+    let phrase = "The quick brown fox jumps over the lazy dog.";
+    let five = grouped_by_name("atbash", "", Grouping::Blocks(5)).unwrap();
+    assert_eq!(five.encrypt(phrase), "gsvjf rxpyi ldmul cqfnk hlevi gsvoz abwlt");
+    let three = grouped_by_name("affine", "5,7", Grouping::Blocks(3)).unwrap();
+    assert_eq!(three.decrypt(&three.encrypt("mindblowingly")), "mindblowingly");
+    let rot = grouped_by_name("rot", "1", Grouping::Blocks(4)).unwrap();
+    assert_eq!(rot.encrypt("abc def gh"), "bcde fghi");
+    assert_eq!(rot.decrypt("bcde fghi"), "abcdefgh");
+    let plain = grouped_by_name("atbash", "", Grouping::Continuous).unwrap();
+    assert_eq!(plain.encrypt("mindblowingly"), "nrmwyoldrmtob");
+}
@@ -1,23 +1,32 @@
 
-use crate::cypher::PLAIN;
+use crate::cypher::{alphabet::Alphabet, Cipher};
+#[cfg(test)]
+use crate::cypher::alphabet::{Case, Unknown, FRENCH};
 
-fn rotate(input: &str, key: u8) -> String {
-    let (str1, str2) = PLAIN.split_at(key as usize % PLAIN.len());
-    let rot_alpha = Vec::from_iter((str2.to_owned() + str1).chars());
-    let crypted = input.chars().map(|c| {
-        if c.is_ascii_alphabetic() {
-            let is_upper = c.is_ascii_uppercase();
-            let cara = if is_upper { c.to_ascii_lowercase() } else { c };
-            if let Some(pos) = PLAIN.chars().position(|c| c == cara) {
-                if is_upper {
-                    return rot_alpha[pos].to_ascii_uppercase(); }
-                    else { return rot_alpha[pos]; }
-            }
-        }
-        c
-    }).collect::<String>();
+// https://en.wikipedia.org/wiki/Caesar_cipher
+pub(crate) struct Rotation {
+    alphabet: Alphabet,
+    key: usize,
+}
+
+impl Rotation {
+    pub(crate) fn new(key: usize, alphabet: Alphabet) -> Self {
+        Rotation { key: key % alphabet.len(), alphabet }
+    }
+}
+
+impl Cipher for Rotation {
+    fn encrypt(&self, plain: &str) -> String {
+        self.alphabet.map(plain, |r| r + self.key)
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        self.alphabet.map(cipher, |r| r + self.alphabet.len() - self.key)
+    }
+}
 
-    crypted
+fn rotate(input: &str, key: u8) -> String {
+    Rotation::new(key as usize, Alphabet::latin_preserving()).encrypt(input)
 }
 
 #[test]
@@ -99,4 +108,21 @@ fn rotate_all_letters() {
     let output = rotate(text, shift_key);
     let expected = "Gur dhvpx oebja sbk whzcf bire gur ynml qbt.";
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+#[test]
+fn rotation_round_trip() {
+    let cipher = Rotation::new(43, Alphabet::latin_preserving());
+    let text = "Let's eat, Grandma!";
+    assert_eq!(cipher.encrypt(text), rotate(text, 17));
+    assert_eq!(cipher.decrypt(&cipher.encrypt(text)), text);
+    assert_eq!(Rotation::new(0, Alphabet::latin_preserving()).decrypt("abc"), "abc");
+}
+#[test]
+fn rotation_with_other_alphabets() {
+    let french = Alphabet::new(FRENCH, Case::Preserve, Unknown::Keep).unwrap();
+    let cipher = Rotation::new(1, french);
+    assert_eq!(cipher.encrypt("Ça y est, zut !"), "Éb z ftu, àvu !");
+    assert_eq!(cipher.decrypt("Éb z ftu, àvu !"), "Ça y est, zut !");
+    let digits = Rotation::new(3, Alphabet::new("0123456789", Case::Sensitive, Unknown::Drop).unwrap());
+    assert_eq!(digits.encrypt("07-89"), "3012");
+}
@@ -0,0 +1,209 @@
+// https://en.wikipedia.org/wiki/Transposition_cipher#Columnar_transposition
+// https://en.wikipedia.org/wiki/Transposition_cipher#Double_transposition
+// https://en.wikipedia.org/wiki/Transposition_cipher#Route_cipher
+//
+// Une transposition ne change aucune lettre, seulement leur ordre : elle se résume à une
+// permutation des positions, appliquée au chiffrement et inversée au déchiffrement.
+// Le texte est manipulé caractère par caractère, sans remplissage de la dernière ligne.
+
+use crate::cypher::Cipher;
+#[cfg(test)]
+use crate::cypher::random::pseudo_random_texts;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Error {
+    EmptyKey,
+    NoColumn,
+}
+
+// order[k] : position dans le clair du k-ième caractère du chiffré
+fn apply(text: &str, order: impl Fn(usize) -> Vec<usize>) -> String {
+    let chars = text.chars().collect::<Vec<char>>();
+    order(chars.len()).into_iter().map(|i| chars[i]).collect()
+}
+
+fn invert(cipher: &str, order: impl Fn(usize) -> Vec<usize>) -> String {
+    let chars = cipher.chars().collect::<Vec<char>>();
+    let mut plain = vec![' '; chars.len()];
+    for (c, i) in chars.into_iter().zip(order(plain.len())) {
+        plain[i] = c;
+    }
+    plain.into_iter().collect()
+}
+
+// Texte écrit en lignes de key.len() caractères, colonnes relues dans l'ordre alphabétique
+// des lettres de la clé (à égalité, de gauche à droite).
+pub(crate) struct Columnar {
+    columns: Vec<usize>,
+}
+
+impl Columnar {
+    pub(crate) fn new(key: &str) -> Result<Self, Error> {
+        let key = key.chars().flat_map(char::to_lowercase).collect::<Vec<char>>();
+        if key.is_empty() { return Err(Error::EmptyKey); }
+        let mut columns = (0..key.len()).collect::<Vec<usize>>();
+        columns.sort_by_key(|i| key[*i]);
+        Ok(Columnar { columns })
+    }
+
+    fn order(&self, len: usize) -> Vec<usize> {
+        let width = self.columns.len();
+        self.columns.iter().flat_map(|col| (*col..len).step_by(width)).collect()
+    }
+}
+
+impl Cipher for Columnar {
+    fn encrypt(&self, plain: &str) -> String {
+        apply(plain, |len| self.order(len))
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        invert(cipher, |len| self.order(len))
+    }
+}
+
+// Deux transpositions en colonnes successives, avec deux clés en principe différentes
+pub(crate) struct DoubleTransposition {
+    first: Columnar,
+    second: Columnar,
+}
+
+impl DoubleTransposition {
+    pub(crate) fn new(first: &str, second: &str) -> Result<Self, Error> {
+        Ok(DoubleTransposition { first: Columnar::new(first)?, second: Columnar::new(second)? })
+    }
+}
+
+impl Cipher for DoubleTransposition {
+    fn encrypt(&self, plain: &str) -> String {
+        self.second.encrypt(&self.first.encrypt(plain))
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        self.first.decrypt(&self.second.decrypt(cipher))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Route {
+    // Spirale dans le sens horaire depuis le coin supérieur gauche
+    Spiral,
+    // Colonnes lues alternativement de haut en bas puis de bas en haut
+    Boustrophedon,
+}
+
+// Texte écrit en lignes de `columns` caractères puis relu en suivant un parcours de la grille.
+// Les cases vides de la dernière ligne sont simplement sautées.
+pub(crate) struct RouteCipher {
+    columns: usize,
+    route: Route,
+}
+
+impl RouteCipher {
+    pub(crate) fn new(columns: usize, route: Route) -> Result<Self, Error> {
+        if columns == 0 { return Err(Error::NoColumn); }
+        Ok(RouteCipher { columns, route })
+    }
+
+    fn order(&self, len: usize) -> Vec<usize> {
+        let width = self.columns;
+        let height = len.div_ceil(width);
+        let mut cells = Vec::<(usize, usize)>::with_capacity(height * width);
+        match self.route {
+            Route::Spiral => {
+                let (mut top, mut left) = (0, 0);
+                let (mut bottom, mut right) = (height as isize - 1, width as isize - 1);
+                while top <= bottom && left <= right {
+                    let (t, b, l, r) = (top as usize, bottom as usize, left as usize, right as usize);
+                    cells.extend((l..=r).map(|c| (t, c)));
+                    cells.extend((t + 1..=b).map(|row| (row, r)));
+                    if top < bottom { cells.extend((l..r).rev().map(|c| (b, c))); }
+                    if left < right { cells.extend((t + 1..b).rev().map(|row| (row, l))); }
+                    (top, bottom, left, right) = (top + 1, bottom - 1, left + 1, right - 1);
+                }
+            },
+            Route::Boustrophedon => {
+                for c in 0..width {
+                    if c % 2 == 0 {
+                        cells.extend((0..height).map(|row| (row, c)));
+                    } else {
+                        cells.extend((0..height).rev().map(|row| (row, c)));
+                    }
+                }
+            },
+        }
+        cells.into_iter()
+            .map(|(row, c)| row * width + c)
+            .filter(|i| *i < len)
+            .collect()
+    }
+}
+
+impl Cipher for RouteCipher {
+    fn encrypt(&self, plain: &str) -> String {
+        apply(plain, |len| self.order(len))
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        invert(cipher, |len| self.order(len))
+    }
+}
+
+#[test]
+fn columnar_zebras() {
+    let cipher = Columnar::new("ZEBRAS").unwrap();
+    assert_eq!(cipher.encrypt("WEAREDISCOVEREDFLEEATONCE"), "EVLNACDTESEAROFODEECWIREE");
+    assert_eq!(cipher.decrypt("EVLNACDTESEAROFODEECWIREE"), "WEAREDISCOVEREDFLEEATONCE");
+}
+#[test]
+fn columnar_with_repeated_key_letters() {
+    let cipher = Columnar::new("tomato").unwrap();
+    let text = "attack postponed until two am";
+    assert_eq!(cipher.decrypt(&cipher.encrypt(text)), text);
+}
+#[test]
+fn double_transposition() {
+    let cipher = DoubleTransposition::new("ZEBRAS", "STRIPE").unwrap();
+    assert_eq!(cipher.encrypt("WEAREDISCOVEREDFLEEATONCE"), "CAEENSOIAEDRLEFWEDREEVTOC");
+    assert_eq!(cipher.decrypt("CAEENSOIAEDRLEFWEDREEVTOC"), "WEAREDISCOVEREDFLEEATONCE");
+}
+#[test]
+fn spiral_route() {
+    let cipher = RouteCipher::new(5, Route::Spiral).unwrap();
+    assert_eq!(cipher.encrypt("WEAREDISCOVEREDFLEEATONCE"), "WEAREODAECNOTFVDISCEEELER");
+    assert_eq!(cipher.decrypt("WEAREODAECNOTFVDISCEEELER"), "WEAREDISCOVEREDFLEEATONCE");
+}
+#[test]
+fn boustrophedon_route() {
+    let cipher = RouteCipher::new(3, Route::Boustrophedon).unwrap();
+    assert_eq!(cipher.encrypt("abcdefgh"), "adghebcf");
+}
+#[test]
+fn invalid_keys() {
+    assert!(matches!(Columnar::new(""), Err(Error::EmptyKey)));
+    assert!(matches!(DoubleTransposition::new("key", ""), Err(Error::EmptyKey)));
+    assert!(matches!(RouteCipher::new(0, Route::Spiral), Err(Error::NoColumn)));
+}
+#[test]
+fn round_trip_property() {
+    let ciphers: Vec<Box<dyn Cipher>> = vec![
+        Box::new(Columnar::new("a").unwrap()),
+        Box::new(Columnar::new("cipher").unwrap()),
+        Box::new(Columnar::new("Été古").unwrap()),
+        Box::new(DoubleTransposition::new("tomato", "zebras").unwrap()),
+        Box::new(RouteCipher::new(1, Route::Spiral).unwrap()),
+        Box::new(RouteCipher::new(4, Route::Spiral).unwrap()),
+        Box::new(RouteCipher::new(7, Route::Spiral).unwrap()),
+        Box::new(RouteCipher::new(3, Route::Boustrophedon).unwrap()),
+    ];
+    for text in pseudo_random_texts(3, 60) {
+        for cipher in &ciphers {
+            let encrypted = cipher.encrypt(&text);
+            let mut sorted = (encrypted.chars().collect::<Vec<char>>(), text.chars().collect::<Vec<char>>());
+            sorted.0.sort_unstable();
+            sorted.1.sort_unstable();
+            assert_eq!(sorted.0, sorted.1);
+            assert_eq!(cipher.decrypt(&encrypted), text);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// https://en.wikipedia.org/wiki/Vigen%C3%A8re_cipher
+// https://en.wikipedia.org/wiki/Beaufort_cipher
+// https://en.wikipedia.org/wiki/Autokey_cipher
+//
+// Chaque lettre est décalée comme dans `rotate`, mais d'une valeur donnée par la lettre
+// courante de la clé. Les caractères hors alphabet ne consomment pas de lettre de clé.
+
+use crate::cypher::{alphabet::Alphabet, Cipher};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Error {
+    EmptyKey,
+    InvalidKey(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    // C = P + K
+    Vigenere,
+    // C = K - P, chiffrement et déchiffrement identiques
+    Beaufort,
+    // C = P - K, soit le déchiffrement de Vigenère
+    VariantBeaufort,
+}
+
+pub(crate) struct Vigenere {
+    alphabet: Alphabet,
+    key: Vec<usize>,
+    kind: Kind,
+    // Autoclé : la clé est prolongée par le texte clair lui-même
+    autokey: bool,
+}
+
+impl Vigenere {
+    pub(crate) fn new(key: &str, kind: Kind, alphabet: Alphabet) -> Result<Self, Error> {
+        if key.is_empty() { return Err(Error::EmptyKey); }
+        let key = key.chars()
+            .map(|c| alphabet.rank(c).ok_or(Error::InvalidKey(c)))
+            .collect::<Result<Vec<usize>, Error>>()?;
+        Ok(Vigenere { alphabet, key, kind, autokey: false })
+    }
+
+    pub(crate) fn autokey(key: &str, alphabet: Alphabet) -> Result<Self, Error> {
+        Ok(Vigenere { autokey: true, ..Vigenere::new(key, Kind::Vigenere, alphabet)? })
+    }
+
+    fn encrypt_rank(&self, p: usize, k: usize) -> usize {
+        let m = self.alphabet.len();
+        match self.kind {
+            Kind::Vigenere => p + k,
+            Kind::Beaufort => k + m - p,
+            Kind::VariantBeaufort => p + m - k,
+        }
+    }
+
+    fn decrypt_rank(&self, c: usize, k: usize) -> usize {
+        let m = self.alphabet.len();
+        match self.kind {
+            Kind::Vigenere => (c + m - k) % m,
+            Kind::Beaufort => (k + m - c) % m,
+            Kind::VariantBeaufort => (c + k) % m,
+        }
+    }
+
+    // Rang de clé pour la i-ème lettre : la clé en boucle, ou la clé suivie du clair en autoclé
+    fn key_at(&self, i: usize, plain: &[usize]) -> usize {
+        match (self.autokey, i < self.key.len()) {
+            (_, true) => self.key[i],
+            (true, false) => plain[i - self.key.len()],
+            (false, false) => self.key[i % self.key.len()],
+        }
+    }
+}
+
+impl Cipher for Vigenere {
+    fn encrypt(&self, plain: &str) -> String {
+        let mut seen = Vec::<usize>::new();
+        self.alphabet.map(plain, |p| {
+            let c = self.encrypt_rank(p, self.key_at(seen.len(), &seen));
+            seen.push(p);
+            c
+        })
+    }
+
+    fn decrypt(&self, cipher: &str) -> String {
+        let mut recovered = Vec::<usize>::new();
+        self.alphabet.map(cipher, |c| {
+            let p = self.decrypt_rank(c, self.key_at(recovered.len(), &recovered));
+            recovered.push(p);
+            p
+        })
+    }
+}
+
+#[test]
+fn vigenere_lemon() {
+    let cipher = Vigenere::new("lemon", Kind::Vigenere, Alphabet::latin_preserving()).unwrap();
+    assert_eq!(cipher.encrypt("ATTACKATDAWN"), "LXFOPVEFRNHR");
+    assert_eq!(cipher.decrypt("LXFOPVEFRNHR"), "ATTACKATDAWN");
+}
+#[test]
+fn vigenere_preserves_case_and_skips_punctuation() {
+    let cipher = Vigenere::new("LEMON", Kind::Vigenere, Alphabet::latin_preserving()).unwrap();
+    assert_eq!(cipher.encrypt("Attack at dawn!"), "Lxfopv ef rnhr!");
+    assert_eq!(cipher.decrypt("Lxfopv ef rnhr!"), "Attack at dawn!");
+}
+#[test]
+fn single_letter_key_is_a_rotation() {
+    let cipher = Vigenere::new("n", Kind::Vigenere, Alphabet::latin_preserving()).unwrap();
+    assert_eq!(cipher.encrypt("The quick brown fox jumps over the lazy dog."), "Gur dhvpx oebja sbk whzcf bire gur ynml qbt.");
+}
+#[test]
+fn beaufort_is_reciprocal() {
+    let cipher = Vigenere::new("fortification", Kind::Beaufort, Alphabet::latin_preserving()).unwrap();
+    assert_eq!(cipher.encrypt("defendtheeastwallofthecastle"), "ckmpvcpvwpiwujogiuapvwriwuuk");
+    assert_eq!(cipher.encrypt("ckmpvcpvwpiwujogiuapvwriwuuk"), "defendtheeastwallofthecastle");
+    assert_eq!(cipher.decrypt("ckmpvcpvwpiwujogiuapvwriwuuk"), "defendtheeastwallofthecastle");
+}
+#[test]
+fn variant_beaufort_is_vigenere_decryption() {
+    let variant_beaufort = Vigenere::new("lemon", Kind::VariantBeaufort, Alphabet::latin_preserving()).unwrap();
+    let vigenere = Vigenere::new("lemon", Kind::Vigenere, Alphabet::latin_preserving()).unwrap();
+    let text = "Variant Beaufort";
+    assert_eq!(variant_beaufort.encrypt(text), vigenere.decrypt(text));
+    assert_eq!(variant_beaufort.decrypt(&variant_beaufort.encrypt(text)), text);
+}
+#[test]
+fn autokey_queenly() {
+    let cipher = Vigenere::autokey("QUEENLY", Alphabet::latin_preserving()).unwrap();
+    assert_eq!(cipher.encrypt("attack at dawn"), "qnxepv yt wtwp");
+    assert_eq!(cipher.decrypt("qnxepv yt wtwp"), "attack at dawn");
+}
+#[test]
+fn invalid_keys() {
+    assert_eq!(Vigenere::new("", Kind::Vigenere, Alphabet::latin()).err(), Some(Error::EmptyKey));
+    assert_eq!(Vigenere::new("le mon", Kind::Vigenere, Alphabet::latin()).err(), Some(Error::InvalidKey(' ')));
+    assert_eq!(Vigenere::autokey("k3y", Alphabet::latin()).err(), Some(Error::InvalidKey('3')));
+}
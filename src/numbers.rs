@@ -1,4 +1,4 @@
-mod prime;
+pub(crate) mod prime;
 mod square;
 mod armstrong;
 mod collatz_conjecture;
@@ -9,4 +9,6 @@ mod accumulation;
 mod euler;
 mod knapsack;
 mod vlq;
-pub mod bcd;
\ No newline at end of file
+pub mod bcd;
+// Arithmétique entière exacte partagée par numbers et cypher, cf. big_uint.rs
+pub(crate) mod big_uint;
\ No newline at end of file
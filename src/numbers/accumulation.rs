@@ -1,3 +1,7 @@
+use std::thread;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 // mut doit être précisé dans le paramètre pour préciser que l'on souhaite la
 // version FnMut car ce supertrait, implémente aussi Fn qui lui-même implémente FnOnce
 fn map<E, S, F>(input: Vec<E>, mut _function: F) -> Vec<S>
@@ -10,6 +14,78 @@ where F: FnMut(E) -> S  {
     output
 }
 
+// Variante faillible : s'arrête au premier Err, les éléments restants ne sont pas évalués
+fn try_map<E, S, X, F>(input: Vec<E>, mut function: F) -> Result<Vec<S>, X>
+where F: FnMut(E) -> Result<S, X> {
+    let mut output = Vec::<S>::with_capacity(input.len());
+    for i in input {
+        output.push(function(i)?);
+    }
+
+    Ok(output)
+}
+
+// Adaptateur paresseux : la fonction n'est appelée qu'à la consommation de chaque élément
+struct Map<I, F> {
+    iter: I,
+    function: F,
+}
+
+impl<I, S, F> Iterator for Map<I, F>
+where I: Iterator, F: FnMut(I::Item) -> S {
+    type Item = S;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(&mut self.function)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+fn lazy_map<I, S, F>(input: I, function: F) -> Map<I::IntoIter, F>
+where I: IntoIterator, F: FnMut(I::Item) -> S {
+    Map { iter: input.into_iter(), function }
+}
+
+// Même type en entrée et en sortie : chaque élément est remplacé sur place,
+// le tampon du vecteur d'origine est conservé tel quel.
+fn map_in_place<T, F>(input: &mut [T], mut function: F)
+where F: FnMut(&T) -> T {
+    for value in input.iter_mut() {
+        *value = function(value);
+    }
+}
+
+// Répartition par blocs contigus entre les threads, l'ordre est conservé.
+// Tous les threads partagent la même fermeture : elle ne peut pas modifier d'état propre,
+// un état commun passe par un type synchronisé (atomique, Mutex…).
+fn par_map<E, S, F>(input: Vec<E>, function: F) -> Vec<S>
+where E: Send, S: Send, F: Fn(E) -> S + Sync {
+    if input.is_empty() { return Vec::new(); }
+
+    let len = input.len();
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = len.div_ceil(threads);
+    let mut values = input.into_iter();
+    let chunks = (0..len.div_ceil(chunk))
+        .map(|_| values.by_ref().take(chunk).collect::<Vec<E>>())
+        .collect::<Vec<Vec<E>>>();
+
+    let function = &function;
+    thread::scope(|scope| {
+        let handles = chunks.into_iter()
+            .map(|c| scope.spawn(move || map(c, function)))
+            .collect::<Vec<_>>();
+        let mut output = Vec::<S>::with_capacity(len);
+        for handle in handles {
+            output.extend(handle.join().unwrap());
+        }
+        output
+    })
+}
+
 fn square(x: i32) -> i32 {
     x * x
 }
@@ -67,4 +143,64 @@ fn minimal_bounds_on_input_and_output() {
     struct Foo;
     struct Bar;
     map(vec![Foo], |_| Bar);
-}
\ No newline at end of file
+}
+#[test]
+fn try_map_collects_when_all_succeed() {
+    let input = vec!["1", "2", "3"];
+    assert_eq!(try_map(input, |s| s.parse::<i32>()), Ok(vec![1, 2, 3]));
+}
+#[test]
+fn try_map_stops_at_first_error() {
+    let mut calls = 0;
+    let input = vec!["1", "x", "3", "y"];
+    let result = try_map(input, |s| {
+        calls += 1;
+        s.parse::<i32>().map_err(|_| s)
+    });
+    assert_eq!(result, Err("x"));
+    assert_eq!(calls, 2);
+}
+#[test]
+fn lazy_map_is_lazy() {
+    let mut calls = 0;
+    let mut iter = lazy_map(vec![1, 2, 3, 4], |x: i32| {
+        calls += 1;
+        x * 10
+    });
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(iter.next(), Some(10));
+    assert_eq!(iter.next(), Some(20));
+    drop(iter);
+    assert_eq!(calls, 2);
+    assert_eq!(lazy_map(1..=3, square).collect::<Vec<i32>>(), vec![1, 4, 9]);
+}
+#[test]
+fn map_in_place_visits_elements_in_order() {
+    let mut input = vec![2, 3, 4, 5];
+    let mut seen = Vec::new();
+    map_in_place(&mut input, |x| {
+        seen.push(*x);
+        square(*x)
+    });
+    assert_eq!(input, vec![4, 9, 16, 25]);
+    assert_eq!(seen, vec![2, 3, 4, 5]);
+}
+#[test]
+fn par_map_keeps_order() {
+    let input = (0..10_000).collect::<Vec<i64>>();
+    let expected = input.iter().map(|x| x * x - 1).collect::<Vec<i64>>();
+    assert_eq!(par_map(input, |x| x * x - 1), expected);
+    assert_eq!(par_map(Vec::<i32>::new(), square), vec![]);
+    assert_eq!(par_map(vec!["a".to_string()], |s| s.repeat(3)), vec!["aaa".to_string()]);
+}
+#[test]
+fn par_map_with_shared_state() {
+    // Un seul compteur pour tous les threads : chaque élément est compté une fois
+    let seen = AtomicUsize::new(0);
+    let result = par_map(vec![5; 64], |x: i32| {
+        seen.fetch_add(1, Ordering::Relaxed);
+        square(x)
+    });
+    assert_eq!(result, vec![25; 64]);
+    assert_eq!(seen.into_inner(), 64);
+}
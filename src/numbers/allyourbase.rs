@@ -1,3 +1,5 @@
+mod radix;
+mod numeration;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Error {
@@ -6,28 +8,47 @@ enum Error {
     InvalidDigit(u32),
 }
 
+// Conversion par divisions successives sur le vecteur de chiffres : aucun
+// cumul du nombre dans un entier machine, donc pas de dépassement quelle que
+// soit la longueur de l'entrée (le reste intermédiaire tient toujours sur un u64).
 fn convert(number: &[u32], from_base: u32, to_base: u32) -> Result<Vec<u32>, Error> {
     if from_base < 2 { return Err(Error::InvalidInputBase); }
     if to_base < 2 { return Err(Error::InvalidOutputBase); }
-    if number.is_empty() { return Ok(vec![0]); }
+    if let Some(digit) = number.iter().find(|d| **d >= from_base) {
+        return Err(Error::InvalidDigit(*digit));
+    }
 
-    let mut n = number.iter()
-                        .try_rfold((0, 0), |(sum, position), num|
-                            if *num >= from_base { Err(Error::InvalidDigit(*num)) }
-                            else { Ok((sum + *num * from_base.pow(position), position + 1)) })
-                        ?.0;
+    let mut dividend = number.iter()
+                        .skip_while(|d| **d == 0)
+                        .copied()
+                        .collect::<Vec<u32>>();
+    if dividend.is_empty() { return Ok(vec![0]); }
 
     let mut output = Vec::<u32>::new();
-    while n >= to_base {
-        output.push(n.rem_euclid(to_base));
-        n = n.div_euclid(to_base);
+    while !dividend.is_empty() {
+        output.push(long_division(&mut dividend, from_base, to_base));
     }
-    output.push(n);
     output.reverse();
 
     Ok(output)
 }
 
+// Divise sur place le nombre `digits` écrit en base `base` par `divisor` et
+// retourne le reste. Les zéros de tête du quotient sont retirés, le vecteur
+// est donc vide quand le quotient est nul.
+fn long_division(digits: &mut Vec<u32>, base: u32, divisor: u32) -> u32 {
+    let mut remainder: u64 = 0;
+    for digit in digits.iter_mut() {
+        let current = remainder * base as u64 + *digit as u64;
+        *digit = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+    digits.drain(..leading_zeros);
+
+    remainder as u32
+}
+
 #[test]
 fn single_bit_one_to_decimal() {
     let input_base = 2;
@@ -209,4 +230,63 @@ fn output_base_is_zero() {
         convert(input_digits, input_base, output_base),
         Err(Error::InvalidOutputBase)
     );
-}
\ No newline at end of file
+}
+#[test]
+fn large_bases_do_not_overflow() {
+    let input_base = u32::MAX;
+    let input_digits = &[u32::MAX - 1, u32::MAX - 1];
+    let output_base = u32::MAX - 1;
+    assert_eq!(
+        convert(input_digits, input_base, output_base),
+        Ok(vec![1, 2, 0])
+    );
+}
+#[test]
+fn thousand_binary_digits_to_hexadecimal() {
+    let input_base = 2;
+    let input_digits = &[1; 1000];
+    let output_base = 16;
+    let mut output_digits = vec![15; 250];
+    assert_eq!(
+        convert(input_digits, input_base, output_base),
+        Ok(output_digits.clone())
+    );
+    output_digits.insert(0, 0);
+    assert_eq!(
+        convert(&output_digits, output_base, input_base),
+        Ok(vec![1; 1000])
+    );
+}
+#[test]
+fn thousand_decimal_digits_power_of_ten() {
+    let input_base = 10;
+    let mut input_digits = vec![0; 1000];
+    input_digits[0] = 1;
+    let output_base = 100;
+    let mut output_digits = vec![0; 500];
+    output_digits[0] = 10;
+    assert_eq!(
+        convert(&input_digits, input_base, output_base),
+        Ok(output_digits)
+    );
+}
+#[test]
+fn thousand_digits_round_trip() {
+    let input_base = 10;
+    let input_digits = (0..1000).map(|i| (i * 7 + 3) % 10).collect::<Vec<u32>>();
+    let intermediate = convert(&input_digits, input_base, 97).unwrap();
+    assert!(intermediate.iter().all(|d| *d < 97));
+    assert_eq!(
+        convert(&intermediate, 97, input_base),
+        Ok(input_digits)
+    );
+}
+#[test]
+fn invalid_digit_in_long_input() {
+    let mut input_digits = vec![1; 1000];
+    input_digits[999] = 7;
+    assert_eq!(
+        convert(&input_digits, 7, 10),
+        Err(Error::InvalidDigit(7))
+    );
+}
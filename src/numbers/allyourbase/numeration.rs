@@ -0,0 +1,208 @@
+// https://en.wikipedia.org/wiki/Negative_base
+// https://en.wikipedia.org/wiki/Balanced_ternary
+// https://en.wikipedia.org/wiki/Bijective_numeration
+//
+// Les chiffres sont toujours donnés du poids fort au poids faible, comme pour `convert`.
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    InvalidBase(i32),
+    InvalidDigit(i64),
+    Overflow,
+    // Écriture unaire plus longue que UNARY_LIMIT
+    TooLong(u128),
+}
+
+// acc × base + digit, pour |digit| < |base|. Le produit seul peut déborder alors que la somme
+// tient dans un i128 (ex. i128::MIN) : on rapproche alors acc de zéro d'une unité
+// et on reporte base sur le chiffre.
+fn horner_step(acc: i128, base: i128, digit: i128) -> Option<i128> {
+    acc.checked_mul(base)
+        .and_then(|v| v.checked_add(digit))
+        .or_else(|| (acc - acc.signum()).checked_mul(base)?.checked_add(digit + acc.signum() * base))
+}
+
+// Écriture de `n` en base négative (ex. -2 négabinaire, -10 négadécimal).
+// Les chiffres sont toujours positifs, entre 0 et |base| - 1.
+fn to_negative_base(mut n: i128, base: i32) -> Result<Vec<u32>, Error> {
+    if base > -2 { return Err(Error::InvalidBase(base)); }
+    if n == 0 { return Ok(vec![0]); }
+
+    let base = base as i128;
+    let mut output = Vec::<u32>::new();
+    while n != 0 {
+        let mut rem = n % base;
+        n /= base;
+        // Le reste doit rester positif : on emprunte une unité au quotient
+        if rem < 0 {
+            rem -= base;
+            n += 1;
+        }
+        output.push(rem as u32);
+    }
+    output.reverse();
+
+    Ok(output)
+}
+
+fn from_negative_base(digits: &[u32], base: i32) -> Result<i128, Error> {
+    if base > -2 { return Err(Error::InvalidBase(base)); }
+
+    digits.iter().try_fold(0i128, |acc, d| {
+        if *d >= base.unsigned_abs() { return Err(Error::InvalidDigit(i64::from(*d))); }
+        horner_step(acc, base as i128, *d as i128).ok_or(Error::Overflow)
+    })
+}
+
+// Ternaire équilibré : chiffres -1, 0 et 1, le signe est porté par les chiffres eux-mêmes.
+fn to_balanced_ternary(mut n: i128) -> Vec<i8> {
+    if n == 0 { return vec![0]; }
+
+    let mut output = Vec::<i8>::new();
+    while n != 0 {
+        let digit = match n.rem_euclid(3) {
+            2 => -1,
+            r => r as i8,
+        };
+        // (n - digit) / 3 sans risque de dépassement sur les bornes de i128
+        n = n.div_euclid(3) + if digit == -1 { 1 } else { 0 };
+        output.push(digit);
+    }
+    output.reverse();
+
+    output
+}
+
+fn from_balanced_ternary(digits: &[i8]) -> Result<i128, Error> {
+    digits.iter().try_fold(0i128, |acc, d| {
+        if !(-1..=1).contains(d) { return Err(Error::InvalidDigit(i64::from(*d))); }
+        horner_step(acc, 3, *d as i128).ok_or(Error::Overflow)
+    })
+}
+
+// Forme textuelle usuelle du ternaire équilibré : T pour -1
+fn balanced_ternary_to_string(digits: &[i8]) -> String {
+    digits.iter().map(|d| match d {
+        -1 => 'T',
+        0 => '0',
+        _ => '1',
+    }).collect()
+}
+
+// En unaire la longueur de l'écriture est la valeur elle-même : au-delà, on refuse
+// plutôt que d'allouer un vecteur démesuré.
+const UNARY_LIMIT: u128 = 1 << 16;
+
+// Numération bijective en base k : chiffres de 1 à k, pas de zéro.
+// Zéro s'écrit avec la suite vide et la base 1 correspond à l'unaire.
+fn to_bijective(mut n: u128, base: u32) -> Result<Vec<u32>, Error> {
+    if base < 1 { return Err(Error::InvalidBase(base as i32)); }
+    if base == 1 {
+        if n > UNARY_LIMIT { return Err(Error::TooLong(n)); }
+        return Ok(vec![1; n as usize]);
+    }
+
+    let base = base as u128;
+    let mut output = Vec::<u32>::new();
+    while n > 0 {
+        let digit = (n - 1) % base + 1;
+        n = (n - digit) / base;
+        output.push(digit as u32);
+    }
+    output.reverse();
+
+    Ok(output)
+}
+
+fn from_bijective(digits: &[u32], base: u32) -> Result<u128, Error> {
+    if base < 1 { return Err(Error::InvalidBase(base as i32)); }
+
+    digits.iter().try_fold(0u128, |acc, d| {
+        if *d < 1 || *d > base { return Err(Error::InvalidDigit(i64::from(*d))); }
+        acc.checked_mul(base as u128)
+            .and_then(|v| v.checked_add(*d as u128))
+            .ok_or(Error::Overflow)
+    })
+}
+
+#[test]
+fn negabinary() {
+    assert_eq!(to_negative_base(0, -2), Ok(vec![0]));
+    assert_eq!(to_negative_base(6, -2), Ok(vec![1, 1, 0, 1, 0]));
+    assert_eq!(to_negative_base(-6, -2), Ok(vec![1, 1, 1, 0]));
+    assert_eq!(from_negative_base(&[1, 1, 0, 1, 0], -2), Ok(6));
+}
+#[test]
+fn negadecimal() {
+    assert_eq!(to_negative_base(15, -10), Ok(vec![1, 9, 5]));
+    assert_eq!(to_negative_base(-15, -10), Ok(vec![2, 5]));
+    assert_eq!(from_negative_base(&[1, 9, 5], -10), Ok(15));
+}
+#[test]
+fn negative_base_round_trip() {
+    for base in [-2, -3, -7, -10, -16] {
+        for n in (-1000..=1000).chain([i128::MIN, i128::MIN + 1, i128::MAX - 1, i128::MAX]) {
+            let digits = to_negative_base(n, base).unwrap();
+            assert_eq!(from_negative_base(&digits, base), Ok(n));
+        }
+    }
+}
+#[test]
+fn negative_base_errors() {
+    assert_eq!(to_negative_base(5, -1), Err(Error::InvalidBase(-1)));
+    assert_eq!(to_negative_base(5, 2), Err(Error::InvalidBase(2)));
+    assert_eq!(from_negative_base(&[1, 2], -2), Err(Error::InvalidDigit(2)));
+    assert_eq!(from_negative_base(&[1; 200], -2), Err(Error::Overflow));
+}
+#[test]
+fn balanced_ternary() {
+    assert_eq!(to_balanced_ternary(0), vec![0]);
+    assert_eq!(to_balanced_ternary(2), vec![1, -1]);
+    assert_eq!(to_balanced_ternary(-8), vec![-1, 0, 1]);
+    assert_eq!(balanced_ternary_to_string(&to_balanced_ternary(8)), "10T");
+    assert_eq!(from_balanced_ternary(&[1, 0, -1]), Ok(8));
+}
+#[test]
+fn balanced_ternary_round_trip() {
+    for n in -5000..=5000 {
+        assert_eq!(from_balanced_ternary(&to_balanced_ternary(n)), Ok(n));
+    }
+    for n in [i128::MIN, i128::MIN + 1, i128::MAX - 1, i128::MAX] {
+        assert_eq!(from_balanced_ternary(&to_balanced_ternary(n)), Ok(n));
+    }
+}
+#[test]
+fn balanced_ternary_errors() {
+    assert_eq!(from_balanced_ternary(&[1, 2]), Err(Error::InvalidDigit(2)));
+    assert_eq!(from_balanced_ternary(&[1; 100]), Err(Error::Overflow));
+}
+#[test]
+fn bijective_base_26_like_spreadsheet_columns() {
+    assert_eq!(to_bijective(1, 26), Ok(vec![1]));
+    assert_eq!(to_bijective(26, 26), Ok(vec![26]));
+    assert_eq!(to_bijective(27, 26), Ok(vec![1, 1]));
+    assert_eq!(to_bijective(702, 26), Ok(vec![26, 26]));
+    assert_eq!(to_bijective(703, 26), Ok(vec![1, 1, 1]));
+}
+#[test]
+fn bijective_round_trip() {
+    for base in [1, 2, 10, 26] {
+        for n in 0..=300 {
+            let digits = to_bijective(n, base).unwrap();
+            assert!(digits.iter().all(|d| (1..=base).contains(d)));
+            assert_eq!(from_bijective(&digits, base), Ok(n));
+        }
+    }
+    assert_eq!(to_bijective(0, 10), Ok(vec![]));
+}
+#[test]
+fn bijective_errors() {
+    assert_eq!(to_bijective(5, 0), Err(Error::InvalidBase(0)));
+    assert_eq!(from_bijective(&[1, 0, 2], 10), Err(Error::InvalidDigit(0)));
+    assert_eq!(from_bijective(&[3], 2), Err(Error::InvalidDigit(3)));
+    assert_eq!(from_bijective(&[10; 40], 10), Err(Error::Overflow));
+    assert_eq!(from_bijective(&[u32::MAX], 10), Err(Error::InvalidDigit(u32::MAX as i64)));
+    assert_eq!(to_bijective(UNARY_LIMIT, 1).map(|digits| digits.len()), Ok(1 << 16));
+    assert_eq!(to_bijective(UNARY_LIMIT + 1, 1), Err(Error::TooLong(UNARY_LIMIT + 1)));
+    assert_eq!(to_bijective(u128::MAX, 1), Err(Error::TooLong(u128::MAX)));
+}
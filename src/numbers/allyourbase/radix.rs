@@ -0,0 +1,318 @@
+// https://datatracker.ietf.org/doc/html/rfc4648
+// https://www.crockford.com/base32.html
+use super::convert;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    InvalidDigit(char),
+    InvalidChecksum(char),
+    WrongLength,
+}
+
+#[derive(Clone, Copy)]
+enum Scheme {
+    // Les octets forment un seul grand nombre en base 256 (base36, base58, base62...).
+    // Chaque octet nul de tête est conservé sous la forme du premier symbole.
+    Radix,
+    // Découpage par blocs de bits (RFC 4648) : `bytes` octets donnent `symbols` symboles,
+    // le dernier bloc incomplet est complété par le caractère de remplissage.
+    Block { bytes: usize, symbols: usize },
+}
+
+struct Alphabet {
+    symbols: &'static str,
+    scheme: Scheme,
+    padding: Option<char>,
+    ignore_case: bool,
+    // Caractères ambigus acceptés au décodage et leur équivalent
+    aliases: &'static [(char, char)],
+    // Séparateurs ignorés au décodage (ex. tirets de lisibilité)
+    ignored: &'static str,
+    // Symboles supplémentaires du caractère de contrôle (modulo 37 pour Crockford)
+    checksum: Option<&'static str>,
+}
+
+const BASE16: Alphabet = Alphabet {
+    symbols: "0123456789ABCDEF",
+    scheme: Scheme::Block { bytes: 1, symbols: 2 },
+    padding: None,
+    ignore_case: true,
+    aliases: &[],
+    ignored: "",
+    checksum: None,
+};
+
+const BASE32: Alphabet = Alphabet {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    scheme: Scheme::Block { bytes: 5, symbols: 8 },
+    padding: Some('='),
+    ignore_case: false,
+    aliases: &[],
+    ignored: "",
+    checksum: None,
+};
+
+const BASE36: Alphabet = Alphabet {
+    symbols: "0123456789abcdefghijklmnopqrstuvwxyz",
+    scheme: Scheme::Radix,
+    padding: None,
+    ignore_case: true,
+    aliases: &[],
+    ignored: "",
+    checksum: None,
+};
+
+const BASE58: Alphabet = Alphabet {
+    symbols: "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    scheme: Scheme::Radix,
+    padding: None,
+    ignore_case: false,
+    aliases: &[],
+    ignored: "",
+    checksum: None,
+};
+
+const BASE62: Alphabet = Alphabet {
+    symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+    scheme: Scheme::Radix,
+    padding: None,
+    ignore_case: false,
+    aliases: &[],
+    ignored: "",
+    checksum: None,
+};
+
+const CROCKFORD32: Alphabet = Alphabet {
+    symbols: "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+    scheme: Scheme::Radix,
+    padding: None,
+    ignore_case: true,
+    aliases: &[('I', '1'), ('L', '1'), ('O', '0')],
+    ignored: "-",
+    checksum: Some("*~$=U"),
+};
+
+impl Alphabet {
+    fn base(&self) -> u32 {
+        self.symbols.chars().count() as u32
+    }
+
+    fn symbol(&self, value: u32) -> char {
+        self.symbols.chars().nth(value as usize).unwrap()
+    }
+
+    fn value(&self, c: char) -> Result<u32, Error> {
+        let c = self.aliases.iter()
+                    .find(|(alias, _)| *alias == c || (self.ignore_case && alias.eq_ignore_ascii_case(&c)))
+                    .map_or(c, |(_, canonical)| *canonical);
+        self.symbols.chars()
+            .position(|s| s == c || (self.ignore_case && s.to_lowercase().eq(c.to_lowercase())))
+            .map(|p| p as u32)
+            .ok_or(Error::InvalidDigit(c))
+    }
+
+    // Caractère de contrôle : le nombre représenté modulo (base + nombre de symboles supplémentaires)
+    fn check_symbol(&self, bytes: &[u8]) -> Option<char> {
+        let extra = self.checksum?;
+        let modulus = self.base() + extra.chars().count() as u32;
+        let rem = bytes.iter().fold(0, |acc, b| (acc * 256 + *b as u32) % modulus);
+        if rem < self.base() { Some(self.symbol(rem)) }
+        else { extra.chars().nth((rem - self.base()) as usize) }
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        let mut output = match self.scheme {
+            Scheme::Radix => self.encode_radix(bytes),
+            Scheme::Block { bytes: block, symbols } => self.encode_block(bytes, block, symbols),
+        };
+        if let Some(c) = self.check_symbol(bytes) {
+            output.push(c);
+        }
+
+        output
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, Error> {
+        let mut chars = text.chars()
+                            .filter(|c| !self.ignored.contains(*c))
+                            .collect::<Vec<char>>();
+        let check = match self.checksum {
+            Some(_) => Some(chars.pop().ok_or(Error::WrongLength)?),
+            None => None,
+        };
+        let bytes = match self.scheme {
+            Scheme::Radix => self.decode_radix(&chars)?,
+            Scheme::Block { bytes, symbols } => self.decode_block(&chars, bytes, symbols)?,
+        };
+        if let Some(c) = check {
+            let expected = self.check_symbol(&bytes).unwrap();
+            if !expected.eq_ignore_ascii_case(&c) {
+                return Err(Error::InvalidChecksum(c));
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    fn encode_radix(&self, bytes: &[u8]) -> String {
+        let zeros = bytes.iter().take_while(|b| **b == 0).count();
+        let mut output = self.symbol(0).to_string().repeat(zeros);
+        if zeros < bytes.len() {
+            let digits = bytes[zeros..].iter().map(|b| *b as u32).collect::<Vec<u32>>();
+            convert(&digits, 256, self.base())
+                .expect("octets toujours valides en base 256")
+                .into_iter()
+                .for_each(|d| output.push(self.symbol(d)));
+        }
+
+        output
+    }
+
+    fn decode_radix(&self, chars: &[char]) -> Result<Vec<u8>, Error> {
+        let values = chars.iter().map(|c| self.value(*c)).collect::<Result<Vec<u32>, Error>>()?;
+        let zeros = values.iter().take_while(|v| **v == 0).count();
+        let mut output = vec![0; zeros];
+        if zeros < values.len() {
+            convert(&values[zeros..], self.base(), 256)
+                .expect("symboles déjà validés")
+                .into_iter()
+                .for_each(|b| output.push(b as u8));
+        }
+
+        Ok(output)
+    }
+
+    // Nombre de symboles significatifs pour `len` octets d'un bloc
+    fn symbols_for(&self, len: usize) -> usize {
+        let bits = self.base().trailing_zeros() as usize;
+        (len * 8).div_ceil(bits)
+    }
+
+    fn encode_block(&self, bytes: &[u8], block: usize, symbols: usize) -> String {
+        let mut output = String::with_capacity(bytes.len().div_ceil(block) * symbols);
+        for chunk in bytes.chunks(block) {
+            let mut digits = chunk.iter().map(|b| *b as u32).collect::<Vec<u32>>();
+            digits.resize(block, 0);
+            let values = left_pad(convert(&digits, 256, self.base()).unwrap(), symbols);
+            let significant = self.symbols_for(chunk.len());
+            values.iter().take(significant).for_each(|v| output.push(self.symbol(*v)));
+            if let Some(pad) = self.padding {
+                (significant..symbols).for_each(|_| output.push(pad));
+            }
+        }
+
+        output
+    }
+
+    fn decode_block(&self, chars: &[char], block: usize, symbols: usize) -> Result<Vec<u8>, Error> {
+        let end = chars.iter().rposition(|c| Some(*c) != self.padding).map_or(0, |p| p + 1);
+        let mut output = Vec::with_capacity(end / symbols * block + block);
+        for chunk in chars[..end].chunks(symbols) {
+            let len = (0..=block).find(|l| self.symbols_for(*l) == chunk.len())
+                        .ok_or(Error::WrongLength)?;
+            let mut values = chunk.iter().map(|c| self.value(*c)).collect::<Result<Vec<u32>, Error>>()?;
+            values.resize(symbols, 0);
+            let bytes = left_pad(convert(&values, self.base(), 256).unwrap(), block);
+            bytes.iter().take(len).for_each(|b| output.push(*b as u8));
+        }
+
+        Ok(output)
+    }
+}
+
+fn left_pad(digits: Vec<u32>, width: usize) -> Vec<u32> {
+    let mut padded = vec![0; width.saturating_sub(digits.len())];
+    padded.extend(digits);
+    padded
+}
+
+#[test]
+fn base16_encode() {
+    assert_eq!(BASE16.encode(b"foobar"), "666F6F626172");
+    assert_eq!(BASE16.encode(&[0, 0, 1]), "000001");
+}
+#[test]
+fn base16_decode_ignores_case() {
+    assert_eq!(BASE16.decode("666f6F626172"), Ok(b"foobar".to_vec()));
+}
+#[test]
+fn base16_odd_length_is_an_error() {
+    assert_eq!(BASE16.decode("666"), Err(Error::WrongLength));
+}
+#[test]
+fn base32_rfc4648_vectors() {
+    let vectors = [
+        ("", ""),
+        ("f", "MY======"),
+        ("fo", "MZXQ===="),
+        ("foo", "MZXW6==="),
+        ("foob", "MZXW6YQ="),
+        ("fooba", "MZXW6YTB"),
+        ("foobar", "MZXW6YTBOI======"),
+    ];
+    for (plain, encoded) in vectors {
+        assert_eq!(BASE32.encode(plain.as_bytes()), encoded);
+        assert_eq!(BASE32.decode(encoded), Ok(plain.as_bytes().to_vec()));
+    }
+}
+#[test]
+fn base32_invalid_digit() {
+    assert_eq!(BASE32.decode("MZXW1==="), Err(Error::InvalidDigit('1')));
+}
+#[test]
+fn base36_round_trip() {
+    assert_eq!(BASE36.encode(&[255]), "73");
+    assert_eq!(BASE36.encode(b"Hello World!"), "2678lx5gvmsv1dro9b5");
+    assert_eq!(BASE36.decode("2678LX5GVMSV1DRO9B5"), Ok(b"Hello World!".to_vec()));
+}
+#[test]
+fn base58_bitcoin() {
+    assert_eq!(BASE58.encode(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+    assert_eq!(BASE58.decode("2NEpo7TZRRrLZSi2U"), Ok(b"Hello World!".to_vec()));
+}
+#[test]
+fn base58_keeps_leading_zeros() {
+    let bytes = [0, 0, 0, 0x28, 0x7f, 0xb4, 0xcd];
+    assert_eq!(BASE58.encode(&bytes), "111233QC4");
+    assert_eq!(BASE58.decode("111233QC4"), Ok(bytes.to_vec()));
+}
+#[test]
+fn base58_rejects_ambiguous_characters() {
+    assert_eq!(BASE58.decode("2NEpo0"), Err(Error::InvalidDigit('0')));
+    assert_eq!(BASE58.decode("2NEpoI"), Err(Error::InvalidDigit('I')));
+}
+#[test]
+fn base62_round_trip() {
+    assert_eq!(BASE62.encode(b"Hello World!"), "T8dgcjRGkZ3aysdN");
+    assert_eq!(BASE62.decode("T8dgcjRGkZ3aysdN"), Ok(b"Hello World!".to_vec()));
+}
+#[test]
+fn empty_input() {
+    assert_eq!(BASE58.encode(&[]), "");
+    assert_eq!(BASE58.decode(""), Ok(vec![]));
+}
+#[test]
+fn crockford_with_checksum() {
+    assert_eq!(CROCKFORD32.encode(&[0x04, 0xD2]), "16JD");
+    assert_eq!(CROCKFORD32.decode("16JD"), Ok(vec![0x04, 0xD2]));
+}
+#[test]
+fn crockford_decode_aliases_case_and_hyphens() {
+    assert_eq!(CROCKFORD32.decode("l6-jd"), Ok(vec![0x04, 0xD2]));
+    assert_eq!(CROCKFORD32.decode("I6JD"), Ok(vec![0x04, 0xD2]));
+}
+#[test]
+fn crockford_invalid_checksum() {
+    assert_eq!(CROCKFORD32.decode("16JE"), Err(Error::InvalidChecksum('E')));
+}
+#[test]
+fn crockford_check_symbol_outside_alphabet() {
+    // 32 modulo 37 = 32 => premier symbole supplémentaire
+    assert_eq!(CROCKFORD32.encode(&[32]), "10*");
+    assert_eq!(CROCKFORD32.decode("10*"), Ok(vec![32]));
+    assert_eq!(CROCKFORD32.decode("1U*"), Err(Error::InvalidDigit('U')));
+}
+#[test]
+fn crockford_missing_checksum() {
+    assert_eq!(CROCKFORD32.decode(""), Err(Error::WrongLength));
+}
@@ -10,6 +10,123 @@ fn is_armstrong_number(num: u32) -> bool {
         .is_some_and(|sum| sum == num)
 }
 
+// https://oeis.org/A005188
+//
+// Recherche des nombres narcissiques par multiensembles de chiffres : la somme des
+// puissances ne dépend pas de l'ordre des chiffres, il suffit donc d'énumérer les
+// combinaisons avec répétition (C(base + largeur - 1, largeur)) au lieu de tous les nombres.
+struct Search {
+    base: u32,
+    powers: Vec<Option<u128>>, // d^largeur, None si dépassement de u128
+    low: u128,                 // base^(largeur - 1), 0 pour un seul chiffre
+    high: Option<u128>,        // base^largeur exclu, None si au-delà de u128
+    counts: Vec<u32>,
+    found: Vec<u128>,
+}
+
+impl Search {
+    fn digits(&self, mut n: u128, width: u32) -> Vec<u32> {
+        let mut digits = vec![0; width as usize];
+        for d in digits.iter_mut().rev() {
+            *d = (n % self.base as u128) as u32;
+            n /= self.base as u128;
+        }
+        digits
+    }
+
+    // Les chiffres de tête communs aux sommes minimale et maximale sont déjà connus :
+    // s'ils contiennent plus d'occurrences d'un chiffre déjà placé (> digit), la branche est vaine.
+    fn prefix_conflict(&self, min: u128, max: u128, digit: u32, width: u32) -> bool {
+        let (min, max) = (self.digits(min, width), self.digits(max, width));
+        let mut prefix = vec![0; self.base as usize];
+        for (d, _) in min.iter().zip(max.iter()).take_while(|(a, b)| a == b) {
+            prefix[*d as usize] += 1;
+        }
+        ((digit + 1)..self.base).any(|d| prefix[d as usize] > self.counts[d as usize])
+    }
+
+    fn explore(&mut self, digit: u32, remaining: u32, sum: u128, width: u32) {
+        if self.high.is_some_and(|high| sum >= high) { return; }
+        if digit == 0 {
+            self.counts[0] = remaining;
+            if sum >= self.low && self.digit_counts(sum, width) == self.counts {
+                self.found.push(sum);
+            }
+            return;
+        }
+        for count in (0..=remaining).rev() {
+            let Some(added) = self.powers[digit as usize].and_then(|p| p.checked_mul(count as u128)) else { continue };
+            let Some(partial) = sum.checked_add(added) else { continue };
+            let left = remaining - count;
+            // Somme maximale atteignable avec les chiffres restants, tous inférieurs à digit
+            let max = self.powers[digit as usize - 1]
+                        .and_then(|p| p.checked_mul(left as u128))
+                        .and_then(|m| m.checked_add(partial))
+                        .unwrap_or(u128::MAX);
+            if max < self.low { break; }
+            self.counts[digit as usize] = count;
+            let max = max.min(self.high.map_or(u128::MAX, |high| high - 1));
+            if self.prefix_conflict(partial, max, digit - 1, width) {
+                continue;
+            }
+            self.explore(digit - 1, left, partial, width);
+        }
+        self.counts[digit as usize] = 0;
+    }
+
+    fn digit_counts(&self, n: u128, width: u32) -> Vec<u32> {
+        let mut counts = vec![0; self.base as usize];
+        self.digits(n, width).iter().for_each(|d| counts[*d as usize] += 1);
+        counts
+    }
+}
+
+// Tous les nombres narcissiques de `width` chiffres en base `base`, triés (0 compris pour un chiffre)
+fn narcissistic(base: u32, width: u32) -> Vec<u128> {
+    if base < 2 || width == 0 { return vec![]; }
+    let Some(low) = (base as u128).checked_pow(width - 1) else { return vec![] };
+    let low = if width == 1 { 0 } else { low };
+    let mut search = Search {
+        base,
+        powers: (0..base as u128).map(|d| d.checked_pow(width)).collect(),
+        low,
+        high: (base as u128).checked_pow(width),
+        counts: vec![0; base as usize],
+        found: Vec::new(),
+    };
+    search.explore(base - 1, width, 0, width);
+    search.found.sort();
+
+    search.found
+}
+
+// Largeur maximale possible : au-delà, width⋅(base-1)^width < base^(width-1)
+// ou la borne basse dépasse u128
+fn all_narcissistic(base: u32) -> Vec<u128> {
+    (1..)
+        .take_while(|width| match (base as u128).checked_pow(width - 1) {
+            Some(low) => ((base - 1) as u128).checked_pow(*width)
+                            .and_then(|p| p.checked_mul(*width as u128))
+                            .is_none_or(|max| max >= low),
+            None => false,
+        })
+        .flat_map(|width| narcissistic(base, width))
+        .collect()
+}
+
+fn is_narcissistic(num: u128, base: u32) -> bool {
+    let mut digits = Vec::new();
+    let mut n = num;
+    while n > 0 {
+        digits.push(n % base as u128);
+        n /= base as u128;
+    }
+    let width = digits.len().max(1) as u32;
+    digits.iter()
+        .try_fold(0u128, |acc, d| d.checked_pow(width).and_then(|p| p.checked_add(acc)))
+        .is_some_and(|sum| sum == num)
+}
+
 #[test]
 fn zero_is_an_armstrong_number() {
     assert!(is_armstrong_number(0))
@@ -80,3 +197,51 @@ fn ten_digit_non_armstrong_number() {
 fn properly_handles_overflow() {
     assert!(!is_armstrong_number(4_106_098_957));
 }
+
+#[test]
+fn three_digit_narcissistic_numbers() {
+    assert_eq!(narcissistic(10, 3), [153, 370, 371, 407]);
+    assert_eq!(narcissistic(10, 2), []);
+}
+
+#[test]
+fn oeis_a005188_up_to_nine_digits() {
+    let found = (1..=9).flat_map(|width| narcissistic(10, width)).collect::<Vec<u128>>();
+    let expected = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 153, 370, 371, 407, 1634, 8208, 9474, 54748, 92727, 93084,
+        548834, 1741725, 4210818, 9800817, 9926315, 24678050, 24678051, 88593477, 146511208,
+        472335975, 534494836, 912985153,
+    ];
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn generator_matches_brute_force_in_small_bases() {
+    for base in 2..=7u32 {
+        for width in 1..=6u32 {
+            let low = if width == 1 { 0 } else { (base as u128).pow(width - 1) };
+            let expected = (low..(base as u128).pow(width))
+                .filter(|n| is_narcissistic(*n, base))
+                .collect::<Vec<u128>>();
+            assert_eq!(narcissistic(base, width), expected, "base {base} width {width}");
+        }
+    }
+}
+
+#[test]
+fn all_narcissistic_numbers_in_base_3() {
+    assert_eq!(all_narcissistic(3), [0, 1, 2, 5, 8, 17]);
+}
+
+#[test]
+fn twenty_digit_narcissistic_numbers() {
+    assert_eq!(narcissistic(10, 20), [63_105_425_988_599_693_916]);
+}
+
+#[test]
+#[ignore]
+fn full_oeis_a005188_list() {
+    let all = all_narcissistic(10);
+    assert_eq!(all.len(), 89);
+    assert_eq!(all.last(), Some(&115_132_219_018_763_992_565_095_597_973_971_522_401));
+}
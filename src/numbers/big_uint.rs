@@ -0,0 +1,377 @@
+// https://en.wikipedia.org/wiki/Arbitrary-precision_arithmetic
+//
+// Entiers naturels exacts pour les calculs (Pascal, échiquier, sommes de puissances,
+// Diffie-Hellman). Nombre reste le type BCD exposé en sortie, via to_nombre, mais ne sert
+// pas de moteur de calcul : sa division n'est pas implémentée, son addition perd la retenue
+// qui aboutit à un zéro final (5 + 5 donne 1E-1) et un chiffre décimal par quartet rend
+// modpow et div_rem trop lents sur 2048 bits.
+
+use std::{cmp::Ordering, fmt::Display, ops::{Add, Mul}};
+
+use super::bcd::nombre::Nombre;
+
+// Base des chiffres : une puissance de 10 pour passer directement à l'affichage
+// décimal et au BCD de Nombre sans conversion.
+const BASE: u64 = 1_000_000_000;
+
+// Entier naturel de taille arbitraire, chiffres en base 10⁹ du poids faible au poids fort.
+// Aucun chiffre nul de poids fort n'est conservé : zéro correspond au vecteur vide.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct BigUint {
+    chiffres: Vec<u32>,
+}
+
+impl BigUint {
+    pub(crate) fn zero() -> Self {
+        BigUint { chiffres: Vec::new() }
+    }
+
+    pub(crate) fn one() -> Self {
+        BigUint { chiffres: vec![1] }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.chiffres.is_empty()
+    }
+
+    fn trim(&mut self) {
+        while self.chiffres.last() == Some(&0) {
+            self.chiffres.pop();
+        }
+    }
+
+    pub(crate) fn mul_small(&self, factor: u64) -> BigUint {
+        let mut chiffres = Vec::with_capacity(self.chiffres.len() + 3);
+        let mut retenue: u128 = 0;
+        for c in &self.chiffres {
+            let product = *c as u128 * factor as u128 + retenue;
+            chiffres.push((product % BASE as u128) as u32);
+            retenue = product / BASE as u128;
+        }
+        while retenue > 0 {
+            chiffres.push((retenue % BASE as u128) as u32);
+            retenue /= BASE as u128;
+        }
+        let mut product = BigUint { chiffres };
+        product.trim();
+        product
+    }
+
+    // Division longue par un entier machine : (quotient, reste)
+    pub(crate) fn div_rem_small(&self, divisor: u64) -> (BigUint, u64) {
+        let mut chiffres = vec![0; self.chiffres.len()];
+        let mut reste: u128 = 0;
+        for (i, c) in self.chiffres.iter().enumerate().rev() {
+            let current = reste * BASE as u128 + *c as u128;
+            chiffres[i] = (current / divisor as u128) as u32;
+            reste = current % divisor as u128;
+        }
+        let mut quotient = BigUint { chiffres };
+        quotient.trim();
+        (quotient, reste as u64)
+    }
+
+    pub(crate) fn pow_small(base: u64, exponent: u32) -> BigUint {
+        (0..exponent).fold(BigUint::one(), |acc, _| acc.mul_small(base))
+    }
+
+    // None si rhs > self
+    pub(crate) fn checked_sub(&self, rhs: &BigUint) -> Option<BigUint> {
+        if *self < *rhs { return None; }
+        let mut chiffres = Vec::with_capacity(self.chiffres.len());
+        let mut emprunt = 0;
+        for (i, c) in self.chiffres.iter().enumerate() {
+            let sub = *rhs.chiffres.get(i).unwrap_or(&0) as i64 + emprunt;
+            let (diff, next) = if (*c as i64) < sub { (*c as i64 + BASE as i64 - sub, 1) } else { (*c as i64 - sub, 0) };
+            chiffres.push(diff as u32);
+            emprunt = next;
+        }
+        let mut difference = BigUint { chiffres };
+        difference.trim();
+        Some(difference)
+    }
+
+    pub(crate) fn is_even(&self) -> bool {
+        self.chiffres.first().is_none_or(|c| c % 2 == 0)
+    }
+
+    // Division longue (Knuth, algorithme D) : (quotient, reste)
+    pub(crate) fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "division par zéro");
+        if self < divisor { return (BigUint::zero(), self.clone()); }
+        if divisor.chiffres.len() == 1 {
+            let (quotient, reste) = self.div_rem_small(divisor.chiffres[0] as u64);
+            return (quotient, BigUint::from(reste as u128));
+        }
+
+        // Normalisation : le chiffre de tête du diviseur doit valoir au moins BASE / 2
+        let d = BASE / (*divisor.chiffres.last().unwrap() as u64 + 1);
+        let v = divisor.mul_small(d).chiffres.into_iter().map(i64::from).collect::<Vec<i64>>();
+        let mut u = self.mul_small(d).chiffres.into_iter().map(i64::from).collect::<Vec<i64>>();
+        u.resize(self.chiffres.len() + 1, 0);
+        let (n, base) = (v.len(), BASE as i64);
+        let mut chiffres = vec![0; u.len() - n];
+
+        for j in (0..u.len() - n).rev() {
+            let num = u[j + n] * base + u[j + n - 1];
+            let (mut qhat, mut rhat) = (num / v[n - 1], num % v[n - 1]);
+            while qhat >= base || qhat * v[n - 2] > rhat * base + u[j + n - 2] {
+                qhat -= 1;
+                rhat += v[n - 1];
+                if rhat >= base { break; }
+            }
+
+            // u[j..=j + n] -= qhat · v
+            let (mut retenue, mut emprunt) = (0, 0);
+            for i in 0..n {
+                let p = qhat * v[i] + retenue;
+                retenue = p / base;
+                let t = u[i + j] - p % base - emprunt;
+                (u[i + j], emprunt) = if t < 0 { (t + base, 1) } else { (t, 0) };
+            }
+            let t = u[j + n] - retenue - emprunt;
+            if t < 0 {
+                // qhat trop grand d'une unité : on rajoute v
+                u[j + n] = t + base;
+                qhat -= 1;
+                let mut retenue = 0;
+                for i in 0..n {
+                    let s = u[i + j] + v[i] + retenue;
+                    (u[i + j], retenue) = (s % base, s / base);
+                }
+                u[j + n] = (u[j + n] + retenue) % base;
+            } else {
+                u[j + n] = t;
+            }
+            chiffres[j] = qhat as u32;
+        }
+
+        let mut quotient = BigUint { chiffres };
+        quotient.trim();
+        let mut reste = BigUint { chiffres: u[..n].iter().map(|c| *c as u32).collect() };
+        reste.trim();
+        (quotient, reste.div_rem_small(d).0)
+    }
+
+    // Chiffres binaires du poids faible au poids fort
+    pub(crate) fn bits(&self) -> Vec<bool> {
+        let mut bits = Vec::new();
+        let mut value = self.clone();
+        while !value.is_zero() {
+            let (quotient, mot) = value.div_rem_small(1 << 32);
+            bits.extend((0..32).map(|i| mot >> i & 1 == 1));
+            value = quotient;
+        }
+        while bits.last() == Some(&false) {
+            bits.pop();
+        }
+        bits
+    }
+
+    // https://en.wikipedia.org/wiki/Modular_exponentiation#Right-to-left_binary_method
+    pub(crate) fn modpow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        if *modulus == BigUint::one() { return BigUint::zero(); }
+        let mut base = self.div_rem(modulus).1;
+        let mut result = BigUint::one();
+        for bit in exponent.bits() {
+            if bit {
+                result = (&result * &base).div_rem(modulus).1;
+            }
+            base = (&base * &base).div_rem(modulus).1;
+        }
+        result
+    }
+
+    // Lecture d'un nombre hexadécimal, les blancs sont ignorés (constantes des RFC)
+    pub(crate) fn from_hex(hex: &str) -> Option<BigUint> {
+        let digits = hex.chars().filter(|c| !c.is_whitespace()).collect::<Vec<char>>();
+        if digits.is_empty() { return None; }
+        digits.chunks(15).try_fold(BigUint::zero(), |acc, chunk| {
+            let value = u64::from_str_radix(&chunk.iter().collect::<String>(), 16).ok()?;
+            Some(&acc.mul_small(1 << (4 * chunk.len())) + &BigUint::from(value as u128))
+        })
+    }
+
+    // Conversion en u128 si la valeur le permet
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        self.chiffres.iter().rev()
+            .try_fold(0u128, |acc, c| acc.checked_mul(BASE as u128)?.checked_add(*c as u128))
+    }
+
+    pub(crate) fn to_nombre(&self) -> Nombre {
+        Nombre::from(self.to_string().as_str())
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut chiffres = Vec::new();
+        while value > 0 {
+            chiffres.push((value % BASE as u128) as u32);
+            value /= BASE as u128;
+        }
+        BigUint { chiffres }
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (long, court) = if self.chiffres.len() >= rhs.chiffres.len() { (self, rhs) } else { (rhs, self) };
+        let mut chiffres = Vec::with_capacity(long.chiffres.len() + 1);
+        let mut retenue = 0;
+        for (i, c) in long.chiffres.iter().enumerate() {
+            let sum = *c as u64 + *court.chiffres.get(i).unwrap_or(&0) as u64 + retenue;
+            chiffres.push((sum % BASE) as u32);
+            retenue = sum / BASE;
+        }
+        if retenue > 0 {
+            chiffres.push(retenue as u32);
+        }
+
+        BigUint { chiffres }
+    }
+}
+
+// Multiplication scolaire, suffisante pour quelques centaines de chiffres
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() { return BigUint::zero(); }
+        let mut produit = vec![0u64; self.chiffres.len() + rhs.chiffres.len()];
+        for (i, a) in self.chiffres.iter().enumerate() {
+            let mut retenue = 0;
+            for (j, b) in rhs.chiffres.iter().enumerate() {
+                let current = produit[i + j] + *a as u64 * *b as u64 + retenue;
+                produit[i + j] = current % BASE;
+                retenue = current / BASE;
+            }
+            produit[i + rhs.chiffres.len()] += retenue;
+        }
+        let mut product = BigUint { chiffres: produit.into_iter().map(|c| c as u32).collect() };
+        product.trim();
+        product
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.chiffres.len().cmp(&other.chiffres.len())
+            .then_with(|| self.chiffres.iter().rev().cmp(other.chiffres.iter().rev()))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut iter = self.chiffres.iter().rev();
+        match iter.next() {
+            Some(c) => write!(f, "{}", c)?,
+            None => return write!(f, "0"),
+        }
+        iter.try_for_each(|c| write!(f, "{:09}", c))
+    }
+}
+
+#[test]
+fn big_uint_from_and_to_u128() {
+    assert!(BigUint::from(0).is_zero());
+    assert_eq!(BigUint::from(u128::MAX).to_u128(), Some(u128::MAX));
+    assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+}
+#[test]
+fn big_uint_add_with_carry() {
+    let max = BigUint::from(u128::MAX);
+    let sum = &max + &BigUint::one();
+    assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+    assert_eq!(sum.to_u128(), None);
+    assert_eq!(&BigUint::zero() + &BigUint::from(1_000_000_000), BigUint::from(1_000_000_000));
+}
+#[test]
+fn big_uint_small_operations() {
+    let power = BigUint::pow_small(2, 200);
+    assert_eq!(power.to_string(), "1606938044258990275541962092341162602522202993782792835301376");
+    let (quotient, reste) = power.div_rem_small(1 << 40);
+    assert_eq!(quotient, BigUint::pow_small(2, 160));
+    assert_eq!(reste, 0);
+    assert_eq!(BigUint::from(1_000_000_007).div_rem_small(10), (BigUint::from(100_000_000), 7));
+    assert_eq!(BigUint::from(123).mul_small(0), BigUint::zero());
+    assert_eq!(BigUint::from(u64::MAX as u128).mul_small(u64::MAX).to_u128(), Some(u64::MAX as u128 * u64::MAX as u128));
+}
+#[test]
+fn big_uint_multiplication() {
+    let a = BigUint::from(u128::MAX);
+    let square = &a * &a;
+    assert_eq!(square.to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+    assert_eq!(&a * &BigUint::zero(), BigUint::zero());
+    assert_eq!(&BigUint::pow_small(10, 40) * &BigUint::pow_small(10, 41), BigUint::pow_small(10, 81));
+}
+#[test]
+fn big_uint_subtraction() {
+    let power = BigUint::pow_small(10, 30);
+    let difference = power.checked_sub(&BigUint::one()).unwrap();
+    assert_eq!(difference.to_string(), "9".repeat(30));
+    assert_eq!(power.checked_sub(&power), Some(BigUint::zero()));
+    assert_eq!(BigUint::one().checked_sub(&power), None);
+}
+#[test]
+fn big_uint_division() {
+    let a = BigUint::pow_small(3, 200);
+    let b = &BigUint::pow_small(7, 50) + &BigUint::from(12_345);
+    let (quotient, reste) = a.div_rem(&b);
+    assert!(reste < b);
+    assert_eq!(&(&quotient * &b) + &reste, a);
+    for i in 1..30 {
+        let a = &BigUint::pow_small(3, 11 * i).mul_small(999_999_999) + &BigUint::from(i as u128);
+        for j in 1..i {
+            let b = &BigUint::pow_small(7, 9 * j) + &BigUint::pow_small(10, 9 * (j / 2));
+            let (quotient, reste) = a.div_rem(&b);
+            assert!(reste < b);
+            assert_eq!(&(&quotient * &b) + &reste, a);
+        }
+    }
+    assert_eq!(BigUint::from(u128::MAX).div_rem(&BigUint::from(u64::MAX as u128)), (BigUint::from(u64::MAX as u128 + 2), BigUint::zero()));
+    assert_eq!(BigUint::from(10).div_rem(&BigUint::pow_small(10, 20)), (BigUint::zero(), BigUint::from(10)));
+    let divisor = BigUint::from(999_999_999_999_999_999_999);
+    for dividend in [u128::MAX, u128::MAX / 3, 999_999_999_999_999_999_998, 1 << 100] {
+        let (quotient, reste) = BigUint::from(dividend).div_rem(&divisor);
+        assert_eq!(quotient.to_u128(), Some(dividend / 999_999_999_999_999_999_999));
+        assert_eq!(reste.to_u128(), Some(dividend % 999_999_999_999_999_999_999));
+    }
+}
+#[test]
+fn big_uint_modpow() {
+    let modulus = BigUint::from(1_000_000_007);
+    assert_eq!(BigUint::from(2).modpow(&BigUint::from(1_000_000_006), &modulus), BigUint::one());
+    // 2^127 - 1 est premier : petit théorème de Fermat
+    let mersenne = BigUint::from((1 << 127) - 1);
+    let exponent = mersenne.checked_sub(&BigUint::one()).unwrap();
+    assert_eq!(BigUint::from(3).modpow(&exponent, &mersenne), BigUint::one());
+    assert_eq!(BigUint::from(5).modpow(&BigUint::zero(), &modulus), BigUint::one());
+    assert_eq!(BigUint::from(5).modpow(&BigUint::from(3), &BigUint::one()), BigUint::zero());
+}
+#[test]
+fn big_uint_hex_and_bits() {
+    assert_eq!(BigUint::from_hex("FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF"), Some(BigUint::from(u128::MAX)));
+    assert_eq!(BigUint::from_hex("1a"), Some(BigUint::from(26)));
+    assert_eq!(BigUint::from_hex("xyz"), None);
+    assert_eq!(BigUint::from(6).bits(), [false, true, true]);
+    assert!(BigUint::zero().bits().is_empty() && BigUint::zero().is_even());
+    assert!(!BigUint::from(1 << 100 | 1).is_even());
+}
+#[test]
+fn big_uint_ordering() {
+    assert!(BigUint::from(1_000_000_000) > BigUint::from(999_999_999));
+    assert!(BigUint::from(2_000_000_001) < BigUint::from(3_000_000_000));
+}
+#[test]
+fn big_uint_to_nombre() {
+    assert_eq!(BigUint::from(1_000_000_007).to_nombre(), Nombre::from("1000000007"));
+}
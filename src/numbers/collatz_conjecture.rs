@@ -1,15 +1,108 @@
+use std::{ops::RangeInclusive, thread};
+
+// Les trajectoires des départs inférieurs à cette borne sont mises en cache
+// (nombre d'étapes et pic) et partagées en lecture par tous les threads.
+const CACHE_LIMIT: u64 = 1 << 20;
+
 fn collatz(mut n: u64) -> Option<u64> {
     for i in 0.. {
         match n {
             0 => break,
             1 => return Some(i),
-            pair if pair % 2 == 0 => n /= 2,
-            _ => n = n * 3 + 1,
+            pair if pair.is_multiple_of(2) => n /= 2,
+            _ => n = n.checked_mul(3)?.checked_add(1)?,
         }
     }
     None
 }
 
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    // Le nombre de départs ne tient pas dans un usize ou en mémoire
+    RangeTooLarge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Trajectory {
+    start: u64,
+    steps: u64,
+    peak: u64,
+}
+
+// Cache mémoïsé des (étapes, pic) pour 1..limit, l'indice 0 n'est pas utilisé
+fn build_cache(limit: u64) -> Vec<(u64, u64)> {
+    let mut cache = vec![(0, 0); limit as usize];
+    let mut path = Vec::<u64>::new();
+    for start in 1..limit {
+        let mut n = start;
+        // Remonter jusqu'à une valeur déjà connue (1 ou un départ plus petit)
+        while n != 1 && (n >= limit || n >= start) {
+            path.push(n);
+            n = if n.is_multiple_of(2) { n / 2 } else { 3 * n + 1 };
+        }
+        let (mut steps, mut peak) = if n == 1 { (0, 1) } else { cache[n as usize] };
+        while let Some(m) = path.pop() {
+            steps += 1;
+            peak = peak.max(m);
+            if m < limit {
+                cache[m as usize] = (steps, peak);
+            }
+        }
+    }
+    cache
+}
+
+fn trajectory(start: u64, cache: &[(u64, u64)]) -> Option<Trajectory> {
+    if start == 0 { return None; }
+    let (mut n, mut steps, mut peak) = (start, 0, start);
+    while n >= cache.len() as u64 {
+        n = if n.is_multiple_of(2) { n / 2 } else { n.checked_mul(3)?.checked_add(1)? };
+        steps += 1;
+        peak = peak.max(n);
+    }
+    let (cached_steps, cached_peak) = if n == 1 { (0, 1) } else { cache[n as usize] };
+
+    Some(Trajectory { start, steps: steps + cached_steps, peak: peak.max(cached_peak) })
+}
+
+// Étapes et pic de chaque départ de l'intervalle, None en cas de dépassement de u64
+// (ou pour 0). Les départs sont répartis par blocs contigus entre les threads.
+fn collatz_range(range: RangeInclusive<u64>) -> Result<Vec<Option<Trajectory>>, Error> {
+    let (first, last) = (*range.start(), *range.end());
+    if first > last { return Ok(vec![]); }
+
+    let len = (last - first).checked_add(1)
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or(Error::RangeTooLarge)?;
+    // L'allocation est vérifiée : un intervalle immense est une erreur, pas un abandon
+    let mut output = Vec::new();
+    output.try_reserve_exact(len).map_err(|_| Error::RangeTooLarge)?;
+    output.resize(len, None);
+    let cache = build_cache(CACHE_LIMIT.min(last.saturating_add(1)).max(2));
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = len.div_ceil(threads);
+    thread::scope(|scope| {
+        for (idx, slice) in output.chunks_mut(chunk).enumerate() {
+            let cache = &cache;
+            scope.spawn(move || {
+                let offset = first + (idx * chunk) as u64;
+                for (i, slot) in slice.iter_mut().enumerate() {
+                    *slot = trajectory(offset + i as u64, cache);
+                }
+            });
+        }
+    });
+
+    Ok(output)
+}
+
+// Plus longue trajectoire de l'intervalle, le plus petit départ en cas d'égalité
+fn longest_chain(range: RangeInclusive<u64>) -> Result<Option<Trajectory>, Error> {
+    Ok(collatz_range(range)?.into_iter()
+        .flatten()
+        .reduce(|best, t| if t.steps > best.steps { t } else { best }))
+}
+
 #[test]
 fn zero_steps_for_one() {
     let output = collatz(1);
@@ -40,3 +133,49 @@ fn zero_is_an_error() {
     let expected = None;
     assert_eq!(output, expected);
 }
+#[test]
+fn overflow_is_an_error() {
+    assert_eq!(collatz(u64::MAX), None);
+    assert_eq!(collatz(6_148_914_691_236_517_205), None);
+}
+#[test]
+fn range_matches_single_computation() {
+    let output = collatz_range(1..=5000).unwrap();
+    assert_eq!(output.len(), 5000);
+    for (start, t) in (1..=5000).zip(output) {
+        assert_eq!(t.map(|t| t.steps), collatz(start));
+        assert_eq!(t.map(|t| t.start), Some(start));
+    }
+}
+#[test]
+fn range_reports_peaks() {
+    let output = collatz_range(26..=27).unwrap();
+    assert_eq!(output[0], Some(Trajectory { start: 26, steps: 10, peak: 40 }));
+    assert_eq!(output[1], Some(Trajectory { start: 27, steps: 111, peak: 9232 }));
+}
+#[test]
+fn range_with_zero_and_overflow() {
+    let output = collatz_range(0..=1);
+    assert_eq!(output, Ok(vec![None, Some(Trajectory { start: 1, steps: 0, peak: 1 })]));
+    let output = collatz_range(u64::MAX - 1..=u64::MAX).unwrap();
+    assert_eq!(output[1], None);
+    let start = 10;
+    assert_eq!(collatz_range(start..=start - 1), Ok(vec![]));
+}
+#[test]
+fn range_too_large() {
+    assert_eq!(collatz_range(0..=u64::MAX), Err(Error::RangeTooLarge));
+    assert_eq!(longest_chain(1..=u64::MAX), Err(Error::RangeTooLarge));
+}
+#[test]
+fn range_beyond_cache() {
+    let start = 10_000_000_000;
+    for (n, t) in (start..=start + 200).zip(collatz_range(start..=start + 200).unwrap()) {
+        assert_eq!(t.map(|t| t.steps), collatz(n));
+    }
+}
+#[test]
+fn longest_chain_under_one_million() {
+    let output = longest_chain(1..=999_999);
+    assert_eq!(output.map(|t| t.map(|t| (t.start, t.steps))), Ok(Some((837_799, 524))));
+}
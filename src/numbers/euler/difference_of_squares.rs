@@ -1,3 +1,13 @@
+// https://en.wikipedia.org/wiki/Faulhaber%27s_formula
+// https://en.wikipedia.org/wiki/Bernoulli_number
+
+use crate::numbers::{bcd::nombre::Nombre, big_uint::BigUint, pascal_triangle::binomial};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    Overflow,
+}
+
 fn square_of_sum(n: u32) -> u32 {
     (n * (n + 1) / 2).pow(2)
 }
@@ -10,6 +20,118 @@ fn difference(n: u32) -> u32 {
     square_of_sum(n) - sum_of_squares(n)
 }
 
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+// Rationnel exact et irréductible, dénominateur strictement positif.
+// Toutes les opérations sont vérifiées : None en cas de dépassement de i128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    fn new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 { return None; }
+        let g = gcd(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Some(Ratio { num: sign * num / g, den: sign * den / g })
+    }
+
+    fn integer(n: i128) -> Self {
+        Ratio { num: n, den: 1 }
+    }
+
+    fn checked_add(self, rhs: Ratio) -> Option<Ratio> {
+        let g = gcd(self.den, rhs.den);
+        let num = self.num.checked_mul(rhs.den / g)?.checked_add(rhs.num.checked_mul(self.den / g)?)?;
+        Ratio::new(num, self.den.checked_mul(rhs.den / g)?)
+    }
+
+    fn checked_mul(self, rhs: Ratio) -> Option<Ratio> {
+        // Simplification croisée avant le produit pour repousser les dépassements
+        let (g1, g2) = (gcd(self.num, rhs.den).max(1), gcd(rhs.num, self.den).max(1));
+        Ratio::new((self.num / g1).checked_mul(rhs.num / g2)?, (self.den / g2).checked_mul(rhs.den / g1)?)
+    }
+}
+
+// B₀ … B_p avec la convention B₁ = +1/2, celle de la formule de Faulhaber pour Σ k^p de 1 à n.
+// Récurrence B_m = -1/(m+1) Σ_{k<m} C(m+1, k) B_k, les termes impairs au-delà de B₁ sont nuls.
+fn bernoulli(p: u32) -> Result<Vec<Ratio>, Error> {
+    let mut numbers = vec![Ratio::integer(1)];
+    for m in 1..=p as u64 {
+        if m > 1 && m % 2 == 1 {
+            numbers.push(Ratio::integer(0));
+            continue;
+        }
+        let sum = numbers.iter().enumerate().try_fold(Ratio::integer(0), |acc, (k, b)| {
+            let c = i128::try_from(binomial(m + 1, k as u64)?).ok()?;
+            acc.checked_add(b.checked_mul(Ratio::integer(c))?)
+        }).ok_or(Error::Overflow)?;
+        numbers.push(sum.checked_mul(Ratio::new(-1, m as i128 + 1).unwrap()).ok_or(Error::Overflow)?);
+    }
+    if p >= 1 {
+        numbers[1] = Ratio::new(1, 2).unwrap();
+    }
+    Ok(numbers)
+}
+
+// Σ k^p pour k de 1 à n : (1/(p+1)) Σ_j C(p+1, j) B_j n^(p+1-j).
+// Le tout est ramené au dénominateur commun D⋅(p+1) pour ne manipuler que des entiers,
+// termes positifs et négatifs étant accumulés séparément.
+fn power_sum(n: u64, p: u32) -> Result<BigUint, Error> {
+    let numbers = bernoulli(p)?;
+    let common = numbers.iter().try_fold(1i128, |acc, b| acc.checked_mul(b.den / gcd(acc, b.den)))
+        .ok_or(Error::Overflow)?;
+    let divisor = common.checked_mul(p as i128 + 1)
+        .and_then(|d| u64::try_from(d).ok())
+        .ok_or(Error::Overflow)?;
+
+    let (mut positive, mut negative) = (BigUint::zero(), BigUint::zero());
+    let mut power = BigUint::pow_small(n, 1);
+    // j décroissant : la puissance de n augmente d'un cran à chaque terme
+    for j in (0..=p as u64).rev() {
+        let b = numbers[j as usize];
+        let coefficient = i128::try_from(binomial(p as u64 + 1, j).ok_or(Error::Overflow)?).ok()
+            .and_then(|c| c.checked_mul(b.num))
+            .and_then(|c| c.checked_mul(common / b.den))
+            .ok_or(Error::Overflow)?;
+        let term = &power * &BigUint::from(coefficient.unsigned_abs());
+        if coefficient < 0 { negative = &negative + &term; } else { positive = &positive + &term; }
+        power = power.mul_small(n);
+    }
+    let (sum, reste) = positive.checked_sub(&negative).ok_or(Error::Overflow)?.div_rem_small(divisor);
+    debug_assert_eq!(reste, 0);
+
+    Ok(sum)
+}
+
+fn power_sum_u128(n: u64, p: u32) -> Result<u128, Error> {
+    power_sum(n, p)?.to_u128().ok_or(Error::Overflow)
+}
+
+fn power_sum_nombre(n: u64, p: u32) -> Result<Nombre, Error> {
+    Ok(power_sum(n, p)?.to_nombre())
+}
+
+fn big_difference(n: u64) -> Result<BigUint, Error> {
+    let sum = power_sum(n, 1)?;
+    (&sum * &sum).checked_sub(&power_sum(n, 2)?).ok_or(Error::Overflow)
+}
+
+fn difference_u128(n: u64) -> Result<u128, Error> {
+    big_difference(n)?.to_u128().ok_or(Error::Overflow)
+}
+
+fn difference_nombre(n: u64) -> Result<Nombre, Error> {
+    Ok(big_difference(n)?.to_nombre())
+}
+
 #[test]
 fn square_of_sum_1() {
     assert_eq!(1, square_of_sum(1));
@@ -54,3 +176,35 @@ fn difference_5() {
 fn difference_100() {
     assert_eq!(25_164_150, difference(100));
 }
+#[test]
+fn bernoulli_numbers() {
+    let numbers = bernoulli(12).unwrap();
+    let expected = [(1, 1), (1, 2), (1, 6), (0, 1), (-1, 30), (0, 1), (1, 42), (0, 1), (-1, 30), (0, 1), (5, 66), (0, 1), (-691, 2730)];
+    assert_eq!(numbers, expected.map(|(num, den)| Ratio::new(num, den).unwrap()));
+    assert_eq!(bernoulli(30).unwrap()[30], Ratio::new(8_615_841_276_005, 14_322).unwrap());
+}
+#[test]
+fn power_sums_match_brute_force() {
+    for p in 0..=12 {
+        for n in [0, 1, 2, 10, 57] {
+            let expected = (1..=n as u128).map(|k| k.pow(p)).sum::<u128>();
+            assert_eq!(power_sum_u128(n, p), Ok(expected));
+        }
+    }
+}
+#[test]
+fn power_sums_up_to_ten_to_the_twelve() {
+    let n = 1_000_000_000_000;
+    assert_eq!(power_sum_u128(n, 1), Ok(500_000_000_000_500_000_000_000));
+    assert_eq!(power_sum_u128(n, 2), Ok(333_333_333_333_833_333_333_333_500_000_000_000));
+    assert_eq!(power_sum_u128(n, 3), Err(Error::Overflow));
+    assert_eq!(power_sum_nombre(n, 3), Ok(Nombre::from("250000000000500000000000250000000000000000000000")));
+    assert_eq!(power_sum(n, 10).unwrap().to_string(), "90909090909590909090909924242424242424242424241424242424242424242424243424242424242424242424241924242424242424242424242500000000000");
+}
+#[test]
+fn difference_beyond_u32() {
+    assert_eq!(difference_u128(100), Ok(25_164_150));
+    assert_eq!(difference_u128(1_000_000), Ok(250_000_166_666_416_666_500_000));
+    assert_eq!(difference_u128(1_000_000_000_000), Err(Error::Overflow));
+    assert_eq!(difference_nombre(1_000_000_000_000), Ok(Nombre::from("250000000000166666666666416666666666500000000000")));
+}
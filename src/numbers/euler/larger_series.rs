@@ -1,34 +1,96 @@
+// Découpage en blocs de van Herk / Gil-Werman, utilisé à l'origine pour les filtres min/max
+// https://en.wikipedia.org/wiki/Mathematical_morphology
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Error {
     SpanTooLong,
     InvalidDigit(char),
+    InvalidBase(u32),
+    Overflow,
 }
 
-fn lsp(string_digits: &str, span: usize) -> Result<u64, Error> {
-    if string_digits.len() < span {
-        return Err(Error::SpanTooLong);
+// Opérations associatives appliquées à chaque fenêtre de chiffres
+#[derive(Debug, Clone, Copy)]
+enum Reducer {
+    Sum,
+    Product,
+    Min,
+    Max,
+}
+
+impl Reducer {
+    fn identity(self) -> u64 {
+        match self {
+            Reducer::Sum => 0,
+            Reducer::Product => 1,
+            Reducer::Min => u64::MAX,
+            Reducer::Max => 0,
+        }
+    }
+
+    // None représente un dépassement, absorbé par un zéro dans le cas du produit
+    fn combine(self, a: Option<u64>, b: Option<u64>) -> Option<u64> {
+        match self {
+            Reducer::Product if a == Some(0) || b == Some(0) => Some(0),
+            Reducer::Product => a?.checked_mul(b?),
+            Reducer::Sum => a?.checked_add(b?),
+            Reducer::Min => Some(a?.min(b?)),
+            Reducer::Max => Some(a?.max(b?)),
+        }
+    }
+}
+
+// Réduction de toutes les fenêtres de longueur span en O(n) pour n'importe quelle opération
+// associative, sans inverse : pas de division, donc aucun cas particulier pour les zéros.
+// Découpage en blocs de span éléments avec suffixes et préfixes cumulés dans chaque bloc,
+// une fenêtre à cheval sur deux blocs vaut suffixe(i) ⊕ préfixe(i + span - 1).
+fn sliding_fold<T: Copy>(values: &[T], span: usize, op: impl Fn(T, T) -> T) -> Vec<T> {
+    if span == 0 || span > values.len() { return Vec::new(); }
+
+    let mut prefix = values.to_vec();
+    let mut suffix = values.to_vec();
+    for i in 1..values.len() {
+        if i % span != 0 {
+            prefix[i] = op(prefix[i - 1], values[i]);
+        }
     }
-    let max = string_digits
-        .chars()
-        .collect::<Vec<char>>()
-        .windows(span)
-        .map(|sub|
-            sub.iter().try_fold(1u64, |acc, n| {
-                if let Some(i) = n.to_digit(10) {
-                    Ok(acc * i as u64)
-                } else {
-                    Err(Error::InvalidDigit(*n))
-                }
-            })
-        )
-        .collect::<Vec<Result<u64, Error>>>();
-    let mut iter = max.iter();
-    if iter.any(|f| f.is_err()) {
-        iter.next().unwrap().clone()
-    } else {
-        Ok(*max.iter().flat_map(|c| c).max().unwrap())
+    for i in (0..values.len() - 1).rev() {
+        if (i + 1) % span != 0 {
+            suffix[i] = op(values[i], suffix[i + 1]);
+        }
     }
+
+    (0..=values.len() - span).map(|i| match i % span {
+        0 => suffix[i],
+        _ => op(suffix[i], prefix[i + span - 1]),
+    }).collect()
+}
+
+fn digits(string_digits: &str, base: u32) -> Result<Vec<Option<u64>>, Error> {
+    if !(2..=36).contains(&base) { return Err(Error::InvalidBase(base)); }
+    string_digits.chars()
+        .map(|c| c.to_digit(base).map(|d| Some(d as u64)).ok_or(Error::InvalidDigit(c)))
+        .collect()
+}
+
+fn reduce_windows(string_digits: &str, span: usize, base: u32, reducer: Reducer) -> Result<Vec<u64>, Error> {
+    let values = digits(string_digits, base)?;
+    if values.len() < span { return Err(Error::SpanTooLong); }
+    if span == 0 { return Ok(vec![reducer.identity(); values.len() + 1]); }
+
+    sliding_fold(&values, span, |a, b| reducer.combine(a, b))
+        .into_iter()
+        .map(|w| w.ok_or(Error::Overflow))
+        .collect()
+}
+
+// Plus grande valeur parmi les fenêtres réduites
+fn largest(string_digits: &str, span: usize, base: u32, reducer: Reducer) -> Result<u64, Error> {
+    Ok(reduce_windows(string_digits, span, base, reducer)?.into_iter().max().unwrap_or(reducer.identity()))
+}
+
+fn lsp(string_digits: &str, span: usize) -> Result<u64, Error> {
+    largest(string_digits, span, 10, Reducer::Product)
 }
 
 #[test]
@@ -86,3 +148,38 @@ fn empty_string_and_non_zero_span_is_an_error() {
 fn a_string_with_non_digits_is_an_error() {
     assert_eq!(Err(Error::InvalidDigit('a')), lsp("1234a5", 2));
 }
+#[test]
+fn span_of_zero_is_the_empty_product() {
+    assert_eq!(Ok(1), lsp("123", 0));
+}
+#[test]
+fn other_bases() {
+    assert_eq!(Ok(225), largest("f0ff1", 2, 16, Reducer::Product));
+    assert_eq!(Ok(1), largest("1101", 2, 2, Reducer::Product));
+    assert_eq!(Err(Error::InvalidDigit('2')), largest("1201", 2, 2, Reducer::Product));
+    assert_eq!(Err(Error::InvalidBase(37)), largest("12", 1, 37, Reducer::Product));
+}
+#[test]
+fn other_reducers() {
+    let input = "73167176531330624919";
+    assert_eq!(Ok(vec![11, 10, 14, 14, 15, 14, 18, 14, 9, 7, 7, 6, 9, 8, 12, 15, 14, 19]), reduce_windows(input, 3, 10, Reducer::Sum));
+    assert_eq!(Ok(vec![1, 1, 1, 1, 1, 1, 5, 3, 1, 1, 1, 0, 0, 0, 2, 2, 1, 1]), reduce_windows(input, 3, 10, Reducer::Min));
+    assert_eq!(Ok(9), largest(input, 20, 10, Reducer::Max));
+}
+#[test]
+fn zeros_inside_an_overflowing_span() {
+    let mut input = "9".repeat(100);
+    input.push('0');
+    input.push_str(&"1".repeat(100));
+    assert_eq!(Ok(0), largest(&input, 150, 10, Reducer::Product));
+    assert_eq!(Err(Error::Overflow), largest(&input, 30, 10, Reducer::Product));
+}
+#[test]
+fn rolling_matches_naive_products() {
+    let input = "7316717653133062491922511967442657474235534919493496983520312774506326239578318016984801869478851843";
+    for span in 1..=12 {
+        let chars = input.chars().map(|c| c.to_digit(10).unwrap() as u64).collect::<Vec<u64>>();
+        let expected = chars.windows(span).map(|w| w.iter().product::<u64>()).collect::<Vec<u64>>();
+        assert_eq!(Ok(expected), reduce_windows(input, span, 10, Reducer::Product));
+    }
+}
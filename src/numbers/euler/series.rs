@@ -17,15 +17,45 @@
 //         .collect()
 // }
 
-fn series(digits: &str, len: usize) -> Vec<String> {
-    let mut suites = Vec::<String>::new();
-    if len > 0 && len <= digits.len() {
-        (len..=digits.len()).fold(0, |acc, i| {
-            suites.push(digits[acc..i].to_string());
-            acc + 1
-        });
+// Fenêtres glissantes empruntées à la chaîne d'origine, sans allocation ni vecteur
+// intermédiaire : chaque fenêtre est une tranche &str de la chaîne d'entrée.
+// Les bornes sont des indices d'octets alignés sur les caractères : l'entrée peut être
+// n'importe quelle chaîne UTF-8, une fenêtre compte toujours `len` caractères.
+struct Windows<'a> {
+    digits: &'a str,
+    start: usize,
+    end: Option<usize>,
+}
+
+impl<'a> Windows<'a> {
+    fn new(digits: &'a str, len: usize) -> Self {
+        let end = match len {
+            0 => None,
+            _ => digits.char_indices().map(|(i, c)| i + c.len_utf8()).nth(len - 1),
+        };
+        Windows { digits, start: 0, end }
     }
-    suites
+}
+
+impl<'a> Iterator for Windows<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+        let window = &self.digits[self.start..end];
+        // Avance d'un caractère aux deux extrémités
+        self.start += window.chars().next().map_or(0, char::len_utf8);
+        self.end = self.digits[end..].chars().next().map(|c| end + c.len_utf8());
+        Some(window)
+    }
+}
+
+fn windows(digits: &str, len: usize) -> Windows<'_> {
+    Windows::new(digits, len)
+}
+
+fn series(digits: &str, len: usize) -> Vec<&str> {
+    windows(digits, len).collect()
 }
 
 #[test]
@@ -101,4 +131,22 @@ fn empty_series_is_invalid() {
     let output = series(input, length);
     let expected: &[&str] = &[];
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+#[test]
+fn windows_borrow_from_the_input() {
+    let input = String::from("91849");
+    let mut iter = windows(&input, 2);
+    let first = iter.next().unwrap();
+    assert!(std::ptr::eq(first.as_ptr(), input.as_ptr()));
+    assert_eq!(iter.collect::<Vec<&str>>(), ["18", "84", "49"]);
+}
+#[test]
+fn windows_count_characters_not_bytes() {
+    assert_eq!(series("٣٤٥٦", 3), ["٣٤٥", "٤٥٦"]);
+    assert_eq!(series("é1ü", 2), ["é1", "1ü"]);
+    assert_eq!(series("٣٤", 3), Vec::<&str>::new());
+}
+#[test]
+fn zero_length_windows_are_empty() {
+    assert_eq!(series("123", 0), Vec::<&str>::new());
+}
@@ -1,19 +1,47 @@
+// https://en.wikipedia.org/wiki/Inclusion%E2%80%93exclusion_principle
 
-fn sum_of_multiples(limit: u32, factors: &[u32]) -> u32 {
-    let mut total = Vec::with_capacity(limit as usize);
-    for f in factors {
-        let end = match *f {
-            0 => 0,
-            _ if (limit % *f) == 0 => limit / *f - 1,
-            _ => limit / *f
-        };
-        (1..=end).for_each(|e| {
-            total.push(f * e);
-        });
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
     }
-    total.sort();
-    total.dedup();
-    total.into_iter().sum()
+    a
+}
+
+// Somme des multiples de m strictement inférieurs à limit : m⋅k(k+1)/2
+fn multiples_below(limit: u64, m: u128) -> u128 {
+    let k = (limit as u128 - 1) / m;
+    let triangle = if k.is_multiple_of(2) { k / 2 * (k + 1) } else { k.div_ceil(2) * k };
+    m * triangle
+}
+
+// Parcours des sous-ensembles de facteurs avec le PPCM courant.
+// Dès que le PPCM atteint la limite, aucun sous-ensemble qui le prolonge ne contribue.
+// Les sommes partielles peuvent dépasser u128 près de u64::MAX : l'arithmétique modulaire
+// (wrapping) reste exacte car le résultat final, lui, tient dans u128.
+fn include_exclude(limit: u64, factors: &[u64], lcm: u128) -> u128 {
+    factors.iter().enumerate().fold(0u128, |acc, (i, f)| {
+        let f = *f as u128;
+        let next = lcm / gcd(lcm, f) * f;
+        if next >= limit as u128 {
+            return acc;
+        }
+        // Multiples de next, moins ceux déjà comptés par les facteurs suivants
+        acc.wrapping_add(multiples_below(limit, next)).wrapping_sub(include_exclude(limit, &factors[i + 1..], next))
+    })
+}
+
+fn sum_of_multiples(limit: u64, factors: &[u64]) -> u128 {
+    if limit == 0 { return 0; }
+    // Un facteur multiple d'un autre n'ajoute rien : on ne garde que les facteurs minimaux
+    let mut sorted = factors.iter().copied().filter(|f| *f != 0 && *f < limit).collect::<Vec<u64>>();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let minimal = sorted.iter().enumerate()
+        .filter(|(i, f)| !sorted[..*i].iter().any(|d| f.is_multiple_of(*d)))
+        .map(|(_, f)| *f)
+        .collect::<Vec<u64>>();
+
+    include_exclude(limit, &minimal, 1)
 }
 
 #[test]
@@ -144,3 +172,30 @@ fn solutions_using_include_exclude_must_extend_to_cardinality_greater_than_3() {
     let expected = 39614537;
     assert_eq!(output, expected);
 }
+#[test]
+fn limit_of_a_billion_does_not_allocate() {
+    assert_eq!(sum_of_multiples(1_000_000_000, &[3, 5]), 233_333_333_166_666_668);
+}
+#[test]
+fn limit_of_ten_to_the_eighteen() {
+    let limit = 1_000_000_000_000_000_000;
+    assert_eq!(sum_of_multiples(limit, &[3, 5]), 233_333_333_333_333_333_166_666_666_666_666_668);
+    assert_eq!(sum_of_multiples(limit, &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]), 430_647_953_682_074_852_225_187_742_919_928_471);
+}
+#[test]
+fn near_u64_max() {
+    let limit = u64::MAX;
+    let k = limit as u128 - 1;
+    assert_eq!(sum_of_multiples(limit, &[1]), k / 2 * (k + 1));
+    assert_eq!(sum_of_multiples(limit, &[1, 2, 3]), k / 2 * (k + 1));
+}
+#[test]
+fn matches_brute_force() {
+    let factor_sets: [&[u64]; 5] = [&[2, 3, 5], &[4, 6, 9, 10], &[7, 14, 21, 3], &[12, 18, 30, 45, 50], &[11, 13, 97, 1000]];
+    for factors in factor_sets {
+        for limit in [1, 2, 100, 997, 5000] {
+            let expected = (1..limit).filter(|n| factors.iter().any(|f| n % f == 0)).map(|n| n as u128).sum::<u128>();
+            assert_eq!(sum_of_multiples(limit, factors), expected);
+        }
+    }
+}
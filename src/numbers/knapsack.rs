@@ -1,47 +1,178 @@
-
  // https://en.wikipedia.org/wiki/Knapsack_problem#Solving
+// https://en.wikipedia.org/wiki/Branch_and_bound
+
+use crate::cypher::affine::xgcd;
+
+mod variants;
+
 #[derive(Debug)]
 struct Item {
     weight: u32,
     value: u32,
 }
 
-fn compute(items: &[Item], memoisation: &mut Vec<Vec<i32>>, curr_weight: usize, curr_item_idx: usize) {
-    // La 1ère ligne ne correspond à aucun item, les unwrap_or s'assurera de renvoyer 0
-    if curr_item_idx == 0 || curr_weight == 0 {
-        memoisation[curr_item_idx][curr_weight] = 0;
-       return;
+// Valeur totale et indices (croissants) des items retenus
+#[derive(Debug, PartialEq, Eq)]
+struct Selection {
+    value: u64,
+    indices: Vec<usize>,
+}
+
+// Nombre de cases (items × capacité) au-delà duquel la programmation dynamique
+// laisse la place au branch and bound
+const DP_CELL_LIMIT: u64 = 1 << 26;
+
+// Items utilisables avec leur indice d'origine, poids divisés par le pgcd de tous les poids :
+// la capacité, donc le nombre de colonnes de la programmation dynamique, diminue d'autant.
+fn scaled(max_weight: u32, items: &[Item]) -> (u32, Vec<(usize, Item)>) {
+    let candidates = items.iter().enumerate()
+        .filter(|(_, item)| item.weight <= max_weight)
+        .collect::<Vec<(usize, &Item)>>();
+    let g = match candidates.iter().fold(0, |g, (_, item)| xgcd(g, item.weight as i64).0) {
+        0 => 1,
+        g => g as u32,
+    };
+    let scaled = candidates.into_iter()
+        .map(|(idx, item)| (idx, Item { weight: item.weight / g, value: item.value }))
+        .collect();
+
+    (max_weight / g, scaled)
+}
+
+fn fits_dp(capacity: u32, items: &[(usize, Item)]) -> bool {
+    within_cell_limit(items.len(), capacity as u64 + 1)
+}
+
+// `pieces` lignes de `columns` cases chacune
+fn within_cell_limit(pieces: usize, columns: u64) -> bool {
+    (pieces as u64).checked_mul(columns).is_some_and(|cells| cells <= DP_CELL_LIMIT)
+}
+
+// Programmation dynamique sur une seule ligne, parcourue à rebours pour ne prendre
+// chaque item qu'une fois
+fn rolling_value(capacity: u32, items: &[(usize, Item)]) -> u64 {
+    let mut best = vec![0u64; capacity as usize + 1];
+    for (_, item) in items {
+        for w in (item.weight as usize..=capacity as usize).rev() {
+            best[w] = best[w].max(best[w - item.weight as usize] + item.value as u64);
+        }
+    }
+    best[capacity as usize]
+}
+
+// Même ligne glissante, chaque amélioration est notée dans un bit par couple (pièce, poids)
+// pour remonter le choix ensuite : 1 bit au lieu d'un i32 par case.
+// Les pièces sont des couples (poids, valeur), le résultat donne les positions des pièces prises.
+fn zero_one(capacity: u32, pieces: &[(u64, u64)]) -> (u64, Vec<usize>) {
+    let columns = capacity as usize + 1;
+    let mut best = vec![0u64; columns];
+    let mut taken = vec![vec![0u64; columns.div_ceil(64)]; pieces.len()];
+    for (k, (weight, value)) in pieces.iter().enumerate() {
+        for w in (*weight as usize..columns).rev() {
+            let candidate = best[w - *weight as usize] + value;
+            if candidate > best[w] {
+                best[w] = candidate;
+                taken[k][w / 64] |= 1 << (w % 64);
+            }
+        }
     }
-    // Déterminer si l'élément précédent a bien été calculé sinon le faire
-    if memoisation[curr_item_idx - 1][curr_weight] == -1 {
-        compute(items, memoisation, curr_weight, curr_item_idx - 1);
+
+    let mut w = capacity as usize;
+    let mut chosen = Vec::new();
+    for (k, (weight, _)) in pieces.iter().enumerate().rev() {
+        if taken[k][w / 64] & (1 << (w % 64)) != 0 {
+            chosen.push(k);
+            w -= *weight as usize;
+        }
     }
-    // Si le poids de l'élément actuel dépasse le poids courant, on récupère la valeur max précédente
-    if items[curr_item_idx - 1].weight > curr_weight as u32 {
-        memoisation[curr_item_idx][curr_weight] = memoisation[curr_item_idx - 1][curr_weight];
+
+    (best[capacity as usize], chosen)
+}
+
+fn rolling_selection(capacity: u32, items: &[(usize, Item)]) -> Selection {
+    let pieces = items.iter().map(|(_, item)| (item.weight as u64, item.value as u64)).collect::<Vec<_>>();
+    let (value, chosen) = zero_one(capacity, &pieces);
+    let mut indices = chosen.into_iter().map(|k| items[k].0).collect::<Vec<usize>>();
+    indices.sort_unstable();
+
+    Selection { value, indices }
+}
+
+// Exploration en profondeur des items triés par densité (valeur / poids) décroissante.
+// Borne : la relaxation fractionnaire gloutonne, exacte à l'unité près car les valeurs sont entières.
+struct BranchAndBound<'a> {
+    items: Vec<&'a (usize, Item)>,
+    current: Vec<bool>,
+    best_value: u64,
+    best: Vec<bool>,
+}
+
+impl<'a> BranchAndBound<'a> {
+    fn new(items: &'a [(usize, Item)]) -> Self {
+        let mut items = items.iter().collect::<Vec<_>>();
+        items.sort_by(|(_, a), (_, b)| (b.value as u64 * a.weight as u64).cmp(&(a.value as u64 * b.weight as u64)));
+        let len = items.len();
+        BranchAndBound { items, current: vec![false; len], best_value: 0, best: vec![false; len] }
     }
-    // Si l'élément précédent de poids courant - le poids de l'élément actuel n'a pas été calculé on le fait
-    else {
-        if memoisation[curr_item_idx - 1][curr_weight - items[curr_item_idx - 1].weight as usize] == -1 {
-            compute(items, memoisation, curr_weight - items[curr_item_idx - 1].weight as usize, curr_item_idx - 1);
+
+    fn bound(&self, k: usize, mut room: u64, value: u64) -> u64 {
+        let mut bound = value;
+        for (_, item) in &self.items[k..] {
+            if item.weight as u64 <= room {
+                room -= item.weight as u64;
+                bound += item.value as u64;
+            } else {
+                return bound + item.value as u64 * room / item.weight as u64;
+            }
         }
-        let v_1 = memoisation[curr_item_idx - 1][curr_weight - items[curr_item_idx - 1].weight as usize] + items[curr_item_idx - 1].value as i32;
-        let v_2 = memoisation[curr_item_idx - 1][curr_weight].max(v_1);
-        memoisation[curr_item_idx][curr_weight] = v_2;
+        bound
+    }
+
+    fn search(&mut self, k: usize, room: u64, value: u64) {
+        if value > self.best_value {
+            self.best_value = value;
+            self.best.copy_from_slice(&self.current);
+        }
+        if k == self.items.len() || self.bound(k, room, value) <= self.best_value {
+            return;
+        }
+        let item = &self.items[k].1;
+        if item.weight as u64 <= room {
+            self.current[k] = true;
+            self.search(k + 1, room - item.weight as u64, value + item.value as u64);
+            self.current[k] = false;
+        }
+        self.search(k + 1, room, value);
+    }
+
+    fn solve(mut self, capacity: u32) -> Selection {
+        self.search(0, capacity as u64, 0);
+        let mut indices = self.items.iter().zip(&self.best)
+            .filter(|(_, taken)| **taken)
+            .map(|((idx, _), _)| *idx)
+            .collect::<Vec<usize>>();
+        indices.sort_unstable();
+
+        Selection { value: self.best_value, indices }
     }
 }
 
-// Possibilité d'utiliser le pgcd présent dans affine.rs pour réduire le nombre et l'incrément du poids.
-// L'idéal serait d'utiliser un équivalent de HashMap pour indexer les poids/items mais celui de Rust est 10 * plus lent !!! mais économise pas mal de mémoire sur les itérations proche du résultat
-fn maximum_value(max_weight: u32, items: &[Item]) -> u32 {
-    if max_weight == 0 || items.len() == 0 {
-        return 0;
+fn best_selection(max_weight: u32, items: &[Item]) -> Selection {
+    let (capacity, items) = scaled(max_weight, items);
+    if fits_dp(capacity, &items) {
+        rolling_selection(capacity, &items)
+    } else {
+        BranchAndBound::new(&items).solve(capacity)
     }
-    // Ajouter un item en plus pour la ligne de 0 objet, 0 poids
-    let mut memoisation = vec![vec![-1; max_weight as usize + 1 ]; items.len() + 1];
-    compute(&items, &mut memoisation, max_weight as usize, items.len());
+}
 
-    *memoisation.iter().map(|v| v.iter().max().unwrap()).max().unwrap() as u32
+fn maximum_value(max_weight: u32, items: &[Item]) -> u64 {
+    let (capacity, items) = scaled(max_weight, items);
+    if fits_dp(capacity, &items) {
+        rolling_value(capacity, &items)
+    } else {
+        BranchAndBound::new(&items).solve(capacity).value
+    }
 }
 
 #[test]
@@ -255,4 +386,81 @@ fn test_15_items() {
     let output = maximum_value(max_weight, &items);
     let expected = 1458;
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+
+// Générateur congruentiel pour des jeux d'essai reproductibles
+#[cfg(test)]
+fn pseudo_random_items(count: usize, max_weight: u32, max_value: u32, seed: u64) -> Vec<Item> {
+    let mut state = seed;
+    let mut next = move |bound: u32| {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        ((state >> 33) % bound as u64) as u32 + 1
+    };
+    (0..count).map(|_| Item { weight: next(max_weight), value: next(max_value) }).collect()
+}
+#[cfg(test)]
+fn brute_force(max_weight: u32, items: &[Item]) -> u64 {
+    (0u32..1 << items.len())
+        .filter_map(|mask| {
+            let chosen = items.iter().enumerate().filter(|(i, _)| mask & (1 << i) != 0);
+            let (w, v) = chosen.fold((0u64, 0u64), |(w, v), (_, item)| (w + item.weight as u64, v + item.value as u64));
+            (w <= max_weight as u64).then_some(v)
+        })
+        .max()
+        .unwrap_or(0)
+}
+#[cfg(test)]
+fn check_selection(max_weight: u32, items: &[Item], selection: &Selection) {
+    let weight = selection.indices.iter().map(|i| items[*i].weight as u64).sum::<u64>();
+    let value = selection.indices.iter().map(|i| items[*i].value as u64).sum::<u64>();
+    assert!(weight <= max_weight as u64);
+    assert_eq!(value, selection.value);
+}
+#[test]
+fn test_selected_items_are_reconstructed() {
+    let items = [
+        Item { weight: 5, value: 10 },
+        Item { weight: 4, value: 40 },
+        Item { weight: 6, value: 30 },
+        Item { weight: 4, value: 50 },
+    ];
+    assert_eq!(best_selection(10, &items), Selection { value: 90, indices: vec![1, 3] });
+    assert_eq!(best_selection(0, &items), Selection { value: 0, indices: vec![] });
+}
+#[test]
+fn test_weights_are_scaled_by_their_gcd() {
+    let items = [
+        Item { weight: 3_000, value: 7 },
+        Item { weight: 4_500, value: 9 },
+        Item { weight: 6_000, value: 13 },
+    ];
+    let (capacity, scaled_items) = scaled(10_700, &items);
+    assert_eq!(capacity, 7);
+    assert_eq!(scaled_items.iter().map(|(_, i)| i.weight).collect::<Vec<u32>>(), vec![2, 3, 4]);
+    assert_eq!(best_selection(10_700, &items), Selection { value: 22, indices: vec![1, 2] });
+}
+#[test]
+fn test_matches_brute_force() {
+    for seed in 0..30 {
+        let items = pseudo_random_items(12, 40, 100, seed);
+        for max_weight in [0, 15, 60, 200] {
+            let expected = brute_force(max_weight, &items);
+            let selection = best_selection(max_weight, &items);
+            assert_eq!(maximum_value(max_weight, &items), expected);
+            assert_eq!(selection.value, expected);
+            check_selection(max_weight, &items, &selection);
+            let (capacity, scaled_items) = scaled(max_weight, &items);
+            assert_eq!(BranchAndBound::new(&scaled_items).solve(capacity).value, expected);
+        }
+    }
+}
+#[test]
+fn test_capacity_in_the_billions() {
+    let items = pseudo_random_items(18, 1_000_000_000, 1_000_000, 7);
+    let max_weight = 3_000_000_000;
+    let (capacity, scaled_items) = scaled(max_weight, &items);
+    assert!(!fits_dp(capacity, &scaled_items));
+    let selection = best_selection(max_weight, &items);
+    assert_eq!(selection.value, brute_force(max_weight, &items));
+    check_selection(max_weight, &items, &selection);
+}
@@ -0,0 +1,299 @@
+// https://en.wikipedia.org/wiki/Knapsack_problem#Definition
+// https://en.wikipedia.org/wiki/Continuous_knapsack_problem
+// https://en.wikipedia.org/wiki/Knapsack_problem#Multi-dimensional_knapsack_problem
+
+#[cfg(test)]
+use super::{brute_force, pseudo_random_items};
+use super::{scaled, within_cell_limit, zero_one, Item, Selection};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    // Table de programmation dynamique au-delà de DP_CELL_LIMIT cases, même après réduction par le pgcd
+    TableTooLarge,
+}
+
+// Nombre d'exemplaires retenus pour chaque item, dans l'ordre de l'entrée
+#[derive(Debug, PartialEq, Eq)]
+struct Counts {
+    value: u64,
+    counts: Vec<u32>,
+}
+
+// Part prise de chaque item, entre 0 et 1
+#[derive(Debug, PartialEq)]
+struct Fractions {
+    value: f64,
+    fractions: Vec<f64>,
+}
+
+// Exemplaires illimités : la ligne est parcourue dans l'ordre croissant des poids,
+// un item peut donc être repris sur une case déjà améliorée par lui-même.
+// Un item sans poids rendrait la valeur infinie : il est ignoré.
+fn unbounded(max_weight: u32, items: &[Item]) -> Result<Counts, Error> {
+    let (capacity, candidates) = scaled(max_weight, items);
+    if !within_cell_limit(candidates.len(), capacity as u64 + 1) { return Err(Error::TableTooLarge); }
+    let columns = capacity as usize + 1;
+    let mut best = vec![0u64; columns];
+    // Dernier item ajouté pour atteindre chaque case, de quoi remonter la solution
+    let mut last = vec![None::<usize>; columns];
+    for w in 1..columns {
+        for (k, (_, item)) in candidates.iter().enumerate() {
+            let weight = item.weight as usize;
+            if weight == 0 || weight > w { continue; }
+            let candidate = best[w - weight] + item.value as u64;
+            if candidate > best[w] {
+                best[w] = candidate;
+                last[w] = Some(k);
+            }
+        }
+    }
+
+    let mut counts = vec![0; items.len()];
+    let mut w = capacity as usize;
+    while let Some(k) = last[w] {
+        let (idx, item) = &candidates[k];
+        counts[*idx] += 1;
+        w -= item.weight as usize;
+    }
+
+    Ok(Counts { value: best[capacity as usize], counts })
+}
+
+// Exemplaires limités par `available` (même longueur que `items`).
+// Découpage binaire : c exemplaires deviennent des lots de 1, 2, 4, … et le reste,
+// toute quantité de 0 à c s'obtient avec un sous-ensemble de lots, on se ramène au 0/1
+// avec O(log c) pièces par item.
+fn bounded(max_weight: u32, items: &[Item], available: &[u32]) -> Result<Counts, Error> {
+    debug_assert_eq!(items.len(), available.len());
+    let (capacity, candidates) = scaled(max_weight, items);
+    let mut pieces = Vec::<(u64, u64)>::new();
+    let mut origins = Vec::<(usize, u32)>::new();
+    for (idx, item) in &candidates {
+        let (mut left, mut lot) = (available[*idx], 1u32);
+        while left > 0 {
+            let take = lot.min(left);
+            let weight = item.weight as u64 * take as u64;
+            // Un lot plus lourd que le sac ne peut pas faire partie d'une solution
+            if weight <= capacity as u64 {
+                pieces.push((weight, item.value as u64 * take as u64));
+                origins.push((*idx, take));
+            }
+            left -= take;
+            lot = lot.saturating_mul(2);
+        }
+    }
+
+    if !within_cell_limit(pieces.len(), capacity as u64 + 1) { return Err(Error::TableTooLarge); }
+    let (value, chosen) = zero_one(capacity, &pieces);
+    let mut counts = vec![0; items.len()];
+    for k in chosen {
+        let (idx, take) = origins[k];
+        counts[idx] += take;
+    }
+
+    Ok(Counts { value, counts })
+}
+
+// 0/1 sous deux contraintes, poids et volume (`volumes` de même longueur que `items`).
+// Poids et volumes sont réduits chacun par leur pgcd, et chaque amélioration est notée
+// dans un bit par couple (poids, volume) pour chaque item, comme pour `zero_one`.
+fn two_constraints(max_weight: u32, max_volume: u32, items: &[Item], volumes: &[u32]) -> Result<Selection, Error> {
+    debug_assert_eq!(items.len(), volumes.len());
+    let (max_weight, by_weight) = scaled(max_weight, items);
+    let volume_items = by_weight.iter().map(|(idx, item)| Item { weight: volumes[*idx], value: item.value }).collect::<Vec<Item>>();
+    let (max_volume, by_volume) = scaled(max_volume, &volume_items);
+    // (indice d'origine, poids réduit, volume réduit, valeur)
+    let pieces = by_volume.into_iter()
+        .map(|(k, item)| (by_weight[k].0, by_weight[k].1.weight as usize, item.weight as usize, item.value as u64))
+        .collect::<Vec<_>>();
+
+    let cells = (max_weight as u64 + 1).checked_mul(max_volume as u64 + 1);
+    if !cells.is_some_and(|cells| within_cell_limit(pieces.len(), cells)) { return Err(Error::TableTooLarge); }
+    let (rows, columns) = (max_weight as usize + 1, max_volume as usize + 1);
+    let cell = |w: usize, v: usize| w * columns + v;
+    let mut best = vec![0u64; rows * columns];
+    let mut taken = vec![vec![0u64; (rows * columns).div_ceil(64)]; pieces.len()];
+    for (k, (_, weight, volume, value)) in pieces.iter().enumerate() {
+        for w in (*weight..rows).rev() {
+            for v in (*volume..columns).rev() {
+                let candidate = best[cell(w - weight, v - volume)] + value;
+                if candidate > best[cell(w, v)] {
+                    best[cell(w, v)] = candidate;
+                    taken[k][cell(w, v) / 64] |= 1 << (cell(w, v) % 64);
+                }
+            }
+        }
+    }
+
+    let (mut w, mut v) = (rows - 1, columns - 1);
+    let mut indices = Vec::new();
+    for (k, (idx, weight, volume, _)) in pieces.iter().enumerate().rev() {
+        if taken[k][cell(w, v) / 64] & (1 << (cell(w, v) % 64)) != 0 {
+            indices.push(*idx);
+            w -= weight;
+            v -= volume;
+        }
+    }
+    indices.sort_unstable();
+
+    Ok(Selection { value: best[cell(rows - 1, columns - 1)], indices })
+}
+
+// Version continue : glouton par densité (valeur / poids) décroissante,
+// seul le dernier item retenu peut être fractionné.
+fn fractional(max_weight: u32, items: &[Item]) -> Fractions {
+    let mut order = (0..items.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| {
+        let (a, b) = (&items[*a], &items[*b]);
+        (b.value as u64 * a.weight as u64).cmp(&(a.value as u64 * b.weight as u64))
+    });
+
+    let mut fractions = vec![0.0; items.len()];
+    let mut room = max_weight as f64;
+    let mut value = 0.0;
+    for idx in order {
+        let item = &items[idx];
+        let part = if item.weight as f64 <= room { 1.0 } else { room / item.weight as f64 };
+        fractions[idx] = part;
+        value += part * item.value as f64;
+        room -= part * item.weight as f64;
+        if room <= 0.0 { break; }
+    }
+
+    Fractions { value, fractions }
+}
+
+#[cfg(test)]
+fn check_counts(max_weight: u32, items: &[Item], result: &Counts) {
+    let weight = items.iter().zip(&result.counts).map(|(i, c)| i.weight as u64 * *c as u64).sum::<u64>();
+    let value = items.iter().zip(&result.counts).map(|(i, c)| i.value as u64 * *c as u64).sum::<u64>();
+    assert!(weight <= max_weight as u64);
+    assert_eq!(value, result.value);
+}
+// Toutes les combinaisons de quantités de 0 à limits[i]
+#[cfg(test)]
+fn brute_force_counts(max_weight: u32, items: &[Item], limits: &[u32]) -> u64 {
+    let mut best = 0;
+    let mut counts = vec![0u32; items.len()];
+    loop {
+        let weight = items.iter().zip(&counts).map(|(i, c)| i.weight as u64 * *c as u64).sum::<u64>();
+        if weight <= max_weight as u64 {
+            best = best.max(items.iter().zip(&counts).map(|(i, c)| i.value as u64 * *c as u64).sum::<u64>());
+        }
+        // Incrément du compteur multi-base
+        let Some(pos) = (0..items.len()).find(|k| counts[*k] < limits[*k]) else { return best; };
+        counts[pos] += 1;
+        counts[..pos].iter_mut().for_each(|c| *c = 0);
+    }
+}
+#[test]
+fn unbounded_example() {
+    let items = [Item { weight: 5, value: 10 }, Item { weight: 3, value: 7 }, Item { weight: 4, value: 8 }];
+    let result = unbounded(11, &items).unwrap();
+    assert_eq!(result.value, 24);
+    check_counts(11, &items, &result);
+    assert_eq!(unbounded(2, &items).unwrap(), Counts { value: 0, counts: vec![0, 0, 0] });
+}
+#[test]
+fn unbounded_matches_brute_force() {
+    for seed in 0..20 {
+        let items = pseudo_random_items(4, 15, 50, seed);
+        for max_weight in [0, 7, 30, 45] {
+            let limits = items.iter().map(|i| max_weight / i.weight).collect::<Vec<u32>>();
+            let result = unbounded(max_weight, &items).unwrap();
+            assert_eq!(result.value, brute_force_counts(max_weight, &items, &limits));
+            check_counts(max_weight, &items, &result);
+        }
+    }
+}
+#[test]
+fn bounded_matches_brute_force() {
+    for seed in 0..20 {
+        let items = pseudo_random_items(4, 15, 50, seed);
+        let available = [0, 3, 6, 13];
+        for max_weight in [0, 7, 30, 80] {
+            let result = bounded(max_weight, &items, &available).unwrap();
+            assert_eq!(result.value, brute_force_counts(max_weight, &items, &available));
+            assert!(result.counts.iter().zip(&available).all(|(c, a)| c <= a));
+            check_counts(max_weight, &items, &result);
+        }
+    }
+}
+#[test]
+fn bounded_with_single_copies_is_zero_one() {
+    let items = pseudo_random_items(10, 30, 100, 42);
+    let result = bounded(100, &items, &[1; 10]).unwrap();
+    assert_eq!(result.value, brute_force(100, &items));
+}
+#[test]
+fn two_constraints_matches_brute_force() {
+    for seed in 0..20 {
+        let items = pseudo_random_items(10, 20, 60, seed);
+        let volumes = pseudo_random_items(10, 20, 1, seed + 100).iter().map(|i| i.weight).collect::<Vec<u32>>();
+        for (max_weight, max_volume) in [(0, 0), (25, 10), (40, 60), (100, 100)] {
+            let expected = (0u32..1 << items.len())
+                .filter(|mask| {
+                    let chosen = (0..items.len()).filter(|k| mask & (1 << k) != 0);
+                    let (w, v) = chosen.fold((0, 0), |(w, v), k| (w + items[k].weight, v + volumes[k]));
+                    w <= max_weight && v <= max_volume
+                })
+                .map(|mask| (0..items.len()).filter(|k| mask & (1 << k) != 0).map(|k| items[k].value as u64).sum::<u64>())
+                .max()
+                .unwrap();
+            let result = two_constraints(max_weight, max_volume, &items, &volumes).unwrap();
+            assert_eq!(result.value, expected);
+            assert!(result.indices.iter().map(|k| items[*k].weight).sum::<u32>() <= max_weight);
+            assert!(result.indices.iter().map(|k| volumes[*k]).sum::<u32>() <= max_volume);
+            assert_eq!(result.indices.iter().map(|k| items[*k].value as u64).sum::<u64>(), expected);
+        }
+    }
+}
+#[test]
+fn two_constraints_with_large_scaled_capacities() {
+    // 10⁹ × 10⁹ cases sans réduction, 10 × 10 une fois divisé par les pgcd
+    let items = [Item { weight: 300_000_000, value: 5 }, Item { weight: 500_000_000, value: 7 }, Item { weight: 200_000_000, value: 4 }];
+    let volumes = [700_000_000, 200_000_000, 300_000_000];
+    let result = two_constraints(1_000_000_000, 1_000_000_000, &items, &volumes);
+    assert_eq!(result, Ok(Selection { value: 12, indices: vec![0, 1] }));
+}
+#[test]
+fn tables_too_large_are_rejected() {
+    // Poids premiers entre eux : aucune réduction possible
+    let items = [Item { weight: 2, value: 3 }, Item { weight: 3, value: 4 }];
+    assert_eq!(unbounded(u32::MAX, &items), Err(Error::TableTooLarge));
+    assert_eq!(bounded(u32::MAX, &items, &[u32::MAX; 2]), Err(Error::TableTooLarge));
+    assert_eq!(two_constraints(u32::MAX, u32::MAX, &items, &[5, 7]), Err(Error::TableTooLarge));
+    assert_eq!(two_constraints(100_000, 100_000, &items, &[5, 7]), Err(Error::TableTooLarge));
+    assert_eq!(two_constraints(1_000, 1_000, &items, &[5, 7]).map(|s| s.value), Ok(7));
+}
+#[test]
+fn fractional_example() {
+    let items = [Item { weight: 10, value: 60 }, Item { weight: 20, value: 100 }, Item { weight: 30, value: 120 }];
+    assert_eq!(fractional(50, &items), Fractions { value: 240.0, fractions: vec![1.0, 1.0, 2.0 / 3.0] });
+    assert_eq!(fractional(0, &items).value, 0.0);
+}
+#[test]
+fn fractional_matches_brute_force() {
+    // L'optimum continu est atteint avec au plus un item fractionné : on essaie tous les
+    // sous-ensembles pris en entier, complétés par une fraction de n'importe quel autre item.
+    for seed in 0..20 {
+        let items = pseudo_random_items(8, 30, 100, seed);
+        for max_weight in [0, 17, 60, 500] {
+            let mut expected = 0f64;
+            for mask in 0u32..1 << items.len() {
+                let chosen = (0..items.len()).filter(|k| mask & (1 << k) != 0);
+                let (w, v) = chosen.fold((0, 0), |(w, v), k| (w + items[k].weight, v + items[k].value));
+                if w > max_weight { continue; }
+                let room = (max_weight - w) as f64;
+                let extra = (0..items.len())
+                    .filter(|k| mask & (1 << k) == 0)
+                    .map(|k| items[k].value as f64 * (room / items[k].weight as f64).min(1.0))
+                    .fold(0f64, f64::max);
+                expected = expected.max(v as f64 + extra);
+            }
+            let result = fractional(max_weight, &items);
+            assert!((result.value - expected).abs() < 1e-9);
+            assert!(result.fractions.iter().filter(|f| **f > 0.0 && **f < 1.0).count() <= 1);
+        }
+    }
+}
@@ -1,3 +1,75 @@
+use super::big_uint::BigUint;
+use super::bcd::nombre::Nombre;
+use super::prime::prime_factor::{is_prime, mul_mod, pow_mod};
+
+// Au-delà, les factorielles modulo p ne sont plus mises en table pour Lucas
+const LUCAS_TABLE_LIMIT: u64 = 1 << 20;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    // Indice de la première ligne non représentable
+    Overflow(usize),
+}
+
+// Coefficient d'une ligne du triangle : l'addition vérifiée signale le premier
+// dépassement de capacité au lieu de produire une ligne fausse.
+trait Coefficient: Clone {
+    fn one() -> Self;
+    fn checked_sum(&self, rhs: &Self) -> Option<Self>;
+}
+
+impl Coefficient for u32 {
+    fn one() -> Self { 1 }
+    fn checked_sum(&self, rhs: &Self) -> Option<Self> { self.checked_add(*rhs) }
+}
+
+impl Coefficient for u128 {
+    fn one() -> Self { 1 }
+    fn checked_sum(&self, rhs: &Self) -> Option<Self> { self.checked_add(*rhs) }
+}
+
+impl Coefficient for BigUint {
+    fn one() -> Self { BigUint::one() }
+    fn checked_sum(&self, rhs: &Self) -> Option<Self> { Some(self + rhs) }
+}
+
+// Itérateur paresseux des lignes, chacune construite depuis la précédente.
+// Après la dernière ligne représentable, il produit une seule erreur puis s'arrête.
+struct Rows<T> {
+    current: Option<Result<Vec<T>, Error>>,
+}
+
+impl<T: Coefficient> Rows<T> {
+    fn new() -> Self {
+        Rows { current: Some(Ok(vec![T::one()])) }
+    }
+}
+
+impl<T: Coefficient> Iterator for Rows<T> {
+    type Item = Result<Vec<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = match self.current.take()? {
+            Ok(row) => row,
+            Err(error) => return Some(Err(error)),
+        };
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(T::one());
+        for pair in row.windows(2) {
+            match pair[0].checked_sum(&pair[1]) {
+                Some(c) => next.push(c),
+                None => {
+                    self.current = Some(Err(Error::Overflow(row.len())));
+                    return Some(Ok(row));
+                },
+            }
+        }
+        next.push(T::one());
+        self.current = Some(Ok(next));
+
+        Some(Ok(row))
+    }
+}
 
 struct PascalsTriangle {
     row_count: u32
@@ -9,51 +81,111 @@ impl PascalsTriangle {
             row_count
         }
     }
-// essayer de revoir l' insertion avec map et fold
-    fn rows(&self) -> Vec<Vec<u32>> {
-        let mut rows = Vec::<Vec<u32>>::new();
-        match self.row_count {
-            0 => rows,
-            1 => {
-                rows.push(vec![1]);
-                rows
-            },
-            _ => {
-                rows.push(vec![1]);
-                let mut prec_row = &vec![1, 1];
-                for i in 2..=self.row_count as usize {
-                    let mut row = vec![1, 1];
-                    row.reserve(i);
-                    let mid = i.div_euclid(2);
-                    for j in 1..mid as usize {
-                        row.insert(j, prec_row[j - 1] + prec_row[j]);
-                        row.insert(row.len() - j, row[j]);
-                    }
-                    if i % 2 != 0 {
-                        row.insert(mid, prec_row[mid - 1] + prec_row[mid]);
-                    }
-                    rows.push(row);
-                    prec_row = &rows[rows.len() - 1];
-                }
-
-                rows
-            }
+
+    fn iter<T: Coefficient>(&self) -> impl Iterator<Item = Result<Vec<T>, Error>> {
+        Rows::<T>::new().take(self.row_count as usize)
+    }
+
+    // Seules les 35 premières lignes sont représentables en u32, au-delà : erreur
+    // plutôt qu'un triangle tronqué (cf. iter::<u128> ou nombre_rows)
+    fn rows(&self) -> Result<Vec<Vec<u32>>, Error> {
+        self.iter().collect()
+    }
+
+    // Lignes calculées avec BigUint, qui ne déborde jamais, puis converties en BCD
+    fn nombre_rows(&self) -> impl Iterator<Item = Vec<Nombre>> {
+        self.iter::<BigUint>().map_while(Result::ok).map(|row| row.iter().map(BigUint::to_nombre).collect())
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// C(n, k) par produit de fractions simplifiées au fur et à mesure : après division
+// par le pgcd, i' = i / pgcd divise forcément (n - k + i), aucun dépassement intermédiaire.
+pub(crate) fn binomial(n: u64, k: u64) -> Option<u128> {
+    if k > n { return Some(0); }
+    let k = k.min(n - k);
+    (1..=k as u128).try_fold(1u128, |acc, i| {
+        let g = gcd(acc, i);
+        acc.checked_div(g)?.checked_mul((n as u128 - k as u128 + i) / (i / g))
+    })
+}
+
+// https://fr.wikipedia.org/wiki/Th%C3%A9or%C3%A8me_de_Lucas
+// C(n, k) mod p = ∏ C(nᵢ, kᵢ) mod p avec nᵢ, kᵢ les chiffres de n et k en base p
+struct Lucas {
+    p: u64,
+    factorials: Vec<u64>, // vide si p dépasse LUCAS_TABLE_LIMIT
+}
+
+impl Lucas {
+    fn new(p: u64) -> Option<Self> {
+        if !is_prime(p) { return None; }
+        let factorials = if p <= LUCAS_TABLE_LIMIT {
+            (1..p).fold(vec![1], |mut acc, i| {
+                acc.push(mul_mod(acc[acc.len() - 1], i, p));
+                acc
+            })
+        } else { vec![] };
+
+        Some(Lucas { p, factorials })
+    }
+
+    fn inverse(&self, a: u64) -> u64 {
+        pow_mod(a, self.p - 2, self.p)
+    }
+
+    // C(n, k) mod p pour n, k < p
+    fn small_binomial(&self, n: u64, k: u64) -> u64 {
+        if k > n { return 0; }
+        if !self.factorials.is_empty() {
+            let den = mul_mod(self.factorials[k as usize], self.factorials[(n - k) as usize], self.p);
+            return mul_mod(self.factorials[n as usize], self.inverse(den), self.p);
+        }
+        let k = k.min(n - k);
+        let (num, den) = (0..k).fold((1, 1), |(num, den), j|
+            (mul_mod(num, n - j, self.p), mul_mod(den, j + 1, self.p)));
+        mul_mod(num, self.inverse(den), self.p)
+    }
+
+    fn binomial(&self, mut n: u64, mut k: u64) -> u64 {
+        let mut result = 1;
+        while (n > 0 || k > 0) && result != 0 {
+            result = mul_mod(result, self.small_binomial(n % self.p, k % self.p), self.p);
+            n /= self.p;
+            k /= self.p;
         }
+        result
     }
 }
 
+// None si p n'est pas premier
+fn binomial_mod(n: u64, k: u64, p: u64) -> Option<u64> {
+    Some(Lucas::new(p)?.binomial(n, k))
+}
+
+fn row_mod(n: u64, p: u64) -> Option<Vec<u64>> {
+    let lucas = Lucas::new(p)?;
+    Some((0..=n).map(|k| lucas.binomial(n, k)).collect())
+}
+
 #[test]
 fn zero_rows() {
     let pt = PascalsTriangle::new(0);
     let expected: Vec<Vec<u32>> = vec![];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
 fn single_row() {
     let pt = PascalsTriangle::new(1);
     let expected: Vec<Vec<u32>> = vec![vec![1]];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -62,7 +194,7 @@ fn two_rows() {
     let expected: Vec<Vec<u32>> = vec![
         vec![1],
         vec![1, 1]];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -72,7 +204,7 @@ fn three_rows() {
         vec![1],
         vec![1, 1],
         vec![1, 2, 1]];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -83,7 +215,7 @@ fn four_rows() {
         vec![1, 1],
         vec![1, 2, 1],
         vec![1, 3, 3, 1]];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -96,7 +228,7 @@ fn five_rows() {
         vec![1, 3, 3, 1],
         vec![1, 4, 6, 4, 1],
     ];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -110,7 +242,7 @@ fn six_rows() {
         vec![1, 4, 6, 4, 1],
         vec![1, 5, 10, 10, 5, 1],
     ];
-    assert_eq!(pt.rows(), expected);
+    assert_eq!(pt.rows(), Ok(expected));
 }
 
 #[test]
@@ -128,5 +260,85 @@ fn ten_rows() {
         vec![1, 8, 28, 56, 70, 56, 28, 8, 1],
         vec![1, 9, 36, 84, 126, 126, 84, 36, 9, 1],
     ];
-    assert_eq!(pt.rows(), expected);
-}
\ No newline at end of file
+    assert_eq!(pt.rows(), Ok(expected));
+}
+#[test]
+fn u32_rows_report_overflow() {
+    assert_eq!(PascalsTriangle::new(100).rows(), Err(Error::Overflow(35)));
+    assert_eq!(PascalsTriangle::new(36).rows(), Err(Error::Overflow(35)));
+    let rows = PascalsTriangle::new(35).rows().unwrap();
+    assert_eq!(rows.len(), 35);
+    assert_eq!(rows[34][17], 2_333_606_220);
+}
+
+#[test]
+fn lazy_rows_are_built_on_demand() {
+    let row = Rows::<u32>::new().nth(20).unwrap().unwrap();
+    assert_eq!(row.len(), 21);
+    assert_eq!(row[10], 184_756);
+}
+
+#[test]
+fn u128_rows() {
+    let pt = PascalsTriangle::new(131);
+    let last = pt.iter::<u128>().last().unwrap().unwrap();
+    assert_eq!(last[65], 95_067_625_827_960_698_145_584_333_020_095_113_100);
+    assert_eq!(Rows::<u128>::new().take_while(Result::is_ok).count(), 132);
+    assert_eq!(Rows::<u128>::new().nth(132), Some(Err(Error::Overflow(132))));
+    assert_eq!(Rows::<u128>::new().nth(133), None);
+}
+
+#[test]
+fn nombre_rows_without_limit() {
+    let pt = PascalsTriangle::new(201);
+    let last = pt.nombre_rows().last().unwrap();
+    assert_eq!(last.len(), 201);
+    assert_eq!(last[100], Nombre::from("90548514656103281165404177077484163874504589675413336841320"));
+    assert_eq!(last[200], Nombre::from("1"));
+}
+
+#[test]
+fn single_binomial() {
+    assert_eq!(binomial(0, 0), Some(1));
+    assert_eq!(binomial(5, 7), Some(0));
+    assert_eq!(binomial(10, 3), Some(120));
+    assert_eq!(binomial(67, 33), Some(14_226_520_737_620_288_370));
+    assert_eq!(binomial(130, 65), Some(95_067_625_827_960_698_145_584_333_020_095_113_100));
+    assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX as u128));
+    assert_eq!(binomial(1000, 500), None);
+}
+
+#[test]
+fn binomial_matches_rows() {
+    for (n, row) in Rows::<u128>::new().enumerate().take(100) {
+        for (k, value) in row.unwrap().iter().enumerate() {
+            assert_eq!(binomial(n as u64, k as u64), Some(*value));
+        }
+    }
+}
+
+#[test]
+fn binomial_modulo_prime() {
+    assert_eq!(binomial_mod(1000, 500, 7), Some(4));
+    assert_eq!(binomial_mod(1_000_000_000_000_012_345, 1_000_000_012_345, 1_000_000_007), Some(714_779_717));
+    assert_eq!(binomial_mod(10, 3, 4), None);
+}
+
+#[test]
+fn binomial_modulo_prime_matches_exact_values() {
+    for p in [2, 3, 5, 7, 13] {
+        for n in 0..60u64 {
+            for k in 0..=n {
+                assert_eq!(binomial_mod(n, k, p), Some((binomial(n, k).unwrap() % p as u128) as u64));
+            }
+        }
+    }
+}
+
+#[test]
+fn rows_modulo_prime() {
+    assert_eq!(row_mod(20, 5), Some(vec![1, 0, 0, 0, 0, 4, 0, 0, 0, 0, 1, 0, 0, 0, 0, 4, 0, 0, 0, 0, 1]));
+    // Ligne 2^k - 1 : tous les coefficients sont impairs
+    assert!(row_mod((1 << 12) - 1, 2).unwrap().iter().all(|c| *c == 1));
+    assert_eq!(row_mod(5, 1), None);
+}
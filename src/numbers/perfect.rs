@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use crate::numbers::prime::{prime_factor::sigma, prime_generator::Primes};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Classification {
@@ -9,18 +12,101 @@ enum Classification {
 fn classify(num: u64) -> Option<Classification> {
     if num == 0 { return None; }
 
-    match aliquot(num) {
-        x if x == num => Some(Classification::Perfect),
-        x if x < num => Some(Classification::Deficient),
-        _ => Some(Classification::Abundant)
+    Some(classify_sum(num, aliquot(num)))
+}
+
+fn classify_sum(num: u64, aliquot: u128) -> Classification {
+    match aliquot {
+        x if x == num as u128 => Classification::Perfect,
+        x if x < num as u128 => Classification::Deficient,
+        _ => Classification::Abundant
     }
 }
 
-fn aliquot(num: u64) -> u64 {
-    let sum = (1..=num.div_euclid(2)).filter(|i| num % i == 0)
-    .sum();
+// Somme des diviseurs propres déduite de la factorisation : σ(n) - n
+fn aliquot(num: u64) -> u128 {
+    sigma(num) - num as u128
+}
+
+// Nombre d'entiers classés à la fois : la mémoire reste bornée quelle que soit la taille de l'intervalle
+const SEGMENT_SIZE: u64 = 1 << 16;
+
+// Classification de tout l'intervalle, tranche par tranche, sans le matérialiser
+fn classify_range(start: u64, end: u64) -> impl Iterator<Item = (u64, Classification)> {
+    let start = start.max(1);
+    std::iter::successors((start <= end).then_some(start), move |low| {
+        low.checked_add(SEGMENT_SIZE).filter(|next| *next <= end)
+    })
+    .flat_map(move |low| classify_segment(low, low.saturating_add(SEGMENT_SIZE - 1).min(end)))
+}
+
+// Classification d'une tranche en une passe : chaque nombre premier p <= √end
+// est retiré des multiples de la tranche en accumulant σ de façon multiplicative,
+// le reste supérieur à 1 est alors forcément un nombre premier.
+fn classify_segment(start: u64, end: u64) -> Vec<(u64, Classification)> {
+    let mut rest = (start..=end).collect::<Vec<u64>>();
+    let mut sums = vec![1u128; rest.len()];
+    for p in Primes::between(2, end.isqrt()) {
+        // Premier multiple au-delà de u64 : aucun multiple de p dans la tranche
+        let Some(first) = start.div_ceil(p).checked_mul(p) else { continue };
+        for idx in (((first - start) as usize)..rest.len()).step_by(p as usize) {
+            let (mut term, mut power) = (1u128, 1u128);
+            while rest[idx].is_multiple_of(p) {
+                rest[idx] /= p;
+                power *= p as u128;
+                term += power;
+            }
+            sums[idx] *= term;
+        }
+    }
 
-    sum
+    (start..=end).zip(rest.into_iter().zip(sums))
+        .map(|(num, (rest, sum))| {
+            let sum = if rest > 1 { sum * (rest as u128 + 1) } else { sum };
+            (num, classify_sum(num, sum - num as u128))
+        })
+        .collect()
+}
+
+// https://en.wikipedia.org/wiki/Aliquot_sequence
+#[derive(Debug, PartialEq, Eq)]
+enum AliquotSequence {
+    // La suite atteint 0 (après un nombre premier puis 1)
+    Terminating(Vec<u64>),
+    // La suite retombe sur un cycle : parfait (1 terme), amiable (2) ou sociable (> 2)
+    Cyclic { path: Vec<u64>, cycle: Vec<u64> },
+    // Nombre maximum de termes atteint ou terme dépassant u64
+    Unresolved(Vec<u64>),
+}
+
+fn aliquot_sequence(num: u64, max_steps: usize) -> AliquotSequence {
+    let mut terms = vec![num];
+    let mut positions = HashMap::from([(num, 0)]);
+    let mut current = num;
+    for _ in 0..max_steps {
+        if current == 0 {
+            return AliquotSequence::Terminating(terms);
+        }
+        current = match u64::try_from(aliquot(current)) {
+            Ok(next) => next,
+            Err(_) => return AliquotSequence::Unresolved(terms),
+        };
+        if let Some(position) = positions.get(&current) {
+            let cycle = terms.split_off(*position);
+            return AliquotSequence::Cyclic { path: terms, cycle };
+        }
+        positions.insert(current, terms.len());
+        terms.push(current);
+    }
+    if current == 0 { AliquotSequence::Terminating(terms) }
+    else { AliquotSequence::Unresolved(terms) }
+}
+
+fn is_amicable(num: u64) -> bool {
+    match aliquot_sequence(num, 2) {
+        AliquotSequence::Cyclic { path, cycle } => path.is_empty() && cycle.len() == 2,
+        _ => false,
+    }
 }
 
 #[test]
@@ -105,4 +191,98 @@ fn zero_is_rejected_as_it_is_not_a_positive_integer() {
     let input = 0;
     let output = classify(input);
     assert!(output.is_none());
-}
\ No newline at end of file
+}
+#[test]
+fn very_large_perfect_number_is_classified_correctly() {
+    let input = 2_305_843_008_139_952_128;
+    let output = classify(input);
+    let expected = Some(Classification::Perfect);
+    assert_eq!(output, expected);
+}
+#[test]
+fn aliquot_of_a_large_prime_is_one() {
+    assert_eq!(aliquot(2_305_843_009_213_693_951), 1);
+}
+#[test]
+fn range_classification_matches_single_classification() {
+    let output = classify_range(0, 10_000).collect::<Vec<_>>();
+    assert_eq!(output.len(), 10_000);
+    for (num, classification) in output {
+        assert_eq!(Some(classification), classify(num));
+    }
+}
+#[test]
+fn range_classification_far_from_zero() {
+    let start = 1_000_000_000_000;
+    for (num, classification) in classify_range(start, start + 1000) {
+        assert_eq!(Some(classification), classify(num));
+    }
+}
+#[test]
+fn range_classification_finds_perfect_numbers() {
+    let perfects = classify_range(1, 10_000)
+        .filter(|(_, c)| *c == Classification::Perfect)
+        .map(|(num, _)| num)
+        .collect::<Vec<u64>>();
+    assert_eq!(perfects, [6, 28, 496, 8128]);
+}
+#[test]
+fn empty_range() {
+    assert_eq!(classify_range(10, 9).count(), 0);
+    assert_eq!(classify_range(0, 0).count(), 0);
+}
+#[test]
+fn range_classification_across_segments() {
+    let output = classify_range(SEGMENT_SIZE - 500, 2 * SEGMENT_SIZE + 500).collect::<Vec<_>>();
+    assert_eq!(output.len() as u64, SEGMENT_SIZE + 1001);
+    for (num, classification) in output {
+        assert_eq!(Some(classification), classify(num));
+    }
+}
+// Crible jusqu'à 2³² : une minute en debug
+#[test]
+#[ignore]
+fn range_classification_near_u64_max() {
+    let output = classify_range(u64::MAX - 20, u64::MAX).collect::<Vec<_>>();
+    assert_eq!(output.last().map(|(num, _)| *num), Some(u64::MAX));
+    for (num, classification) in output {
+        assert_eq!(Some(classification), classify(num));
+    }
+}
+#[test]
+fn terminating_sequence() {
+    assert_eq!(aliquot_sequence(10, 20), AliquotSequence::Terminating(vec![10, 8, 7, 1, 0]));
+    assert_eq!(aliquot_sequence(12, 20), AliquotSequence::Terminating(vec![12, 16, 15, 9, 4, 3, 1, 0]));
+}
+#[test]
+fn perfect_cycle() {
+    assert_eq!(aliquot_sequence(6, 20), AliquotSequence::Cyclic { path: vec![], cycle: vec![6] });
+    assert_eq!(aliquot_sequence(95, 20), AliquotSequence::Cyclic { path: vec![95, 25], cycle: vec![6] });
+}
+#[test]
+fn amicable_cycle() {
+    assert_eq!(aliquot_sequence(220, 20), AliquotSequence::Cyclic { path: vec![], cycle: vec![220, 284] });
+    assert_eq!(aliquot_sequence(562, 20), AliquotSequence::Cyclic { path: vec![562], cycle: vec![284, 220] });
+    assert!(is_amicable(1184));
+    assert!(!is_amicable(6));
+    assert!(!is_amicable(562));
+}
+#[test]
+fn sociable_cycles() {
+    assert_eq!(
+        aliquot_sequence(12_496, 20),
+        AliquotSequence::Cyclic { path: vec![], cycle: vec![12_496, 14_288, 15_472, 14_536, 14_264] }
+    );
+    assert_eq!(
+        aliquot_sequence(1_264_460, 20),
+        AliquotSequence::Cyclic { path: vec![], cycle: vec![1_264_460, 1_547_860, 1_727_636, 1_305_184] }
+    );
+}
+#[test]
+fn unresolved_sequence() {
+    // 276 est le plus petit nombre dont la suite aliquote reste ouverte
+    match aliquot_sequence(276, 30) {
+        AliquotSequence::Unresolved(terms) => assert_eq!(&terms[..4], &[276, 396, 696, 1104]),
+        other => panic!("Unexpected sequence: {:?}", other),
+    }
+}
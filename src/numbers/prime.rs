@@ -1,2 +1,5 @@
-mod prime_factor;
-mod prime_generator;
+pub(crate) mod prime_factor;
+pub(crate) mod miller_rabin;
+pub(crate) mod prime_generator;
+#[cfg(feature = "big-primes")]
+mod big_primes;
@@ -0,0 +1,424 @@
+// https://en.wikipedia.org/wiki/Montgomery_modular_multiplication
+// https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test
+// https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm#Variants
+// https://en.wikipedia.org/wiki/Baillie%E2%80%93PSW_primality_test
+
+use super::miller_rabin::{strong_probable_prime, Modulus};
+use super::prime_factor;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+const SMALL_PRIMES: [u128; 20] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+// Plus petit pseudo-premier fort pour les 13 premières bases (Sorenson et Webster, 2015) :
+// en dessous, Miller-Rabin sur ces bases est démontré exact.
+const PSI_13: u128 = 3_317_044_064_679_887_385_961_981;
+
+const TRIAL_LIMIT: u128 = 1000;
+// Nombre de différences accumulées avant chaque calcul de pgcd dans Brent
+const BRENT_BATCH: u128 = 128;
+
+// Produit complet de 2 u128 : (poids fort, poids faible)
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_MASK);
+    let (b1, b0) = (b >> 64, b & LOW_MASK);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 64) + (p01 & LOW_MASK) + (p10 & LOW_MASK);
+
+    (p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64), (p00 & LOW_MASK) | (mid << 64))
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Arithmétique modulaire de Montgomery avec R = 2¹²⁸, le module doit être impair.
+// Les valeurs manipulées sont sous forme de Montgomery (x⋅R mod n).
+struct Montgomery {
+    n: u128,
+    n_inv: u128, // n⁻¹ mod 2¹²⁸
+    one: u128,   // R mod n
+    r2: u128,    // R² mod n
+}
+
+impl Montgomery {
+    fn new(n: u128) -> Self {
+        // Newton : chaque itération double le nombre de bits corrects (n⋅n ≡ 1 mod 8 au départ)
+        let mut n_inv = n;
+        for _ in 0..6 {
+            n_inv = n_inv.wrapping_mul(2u128.wrapping_sub(n.wrapping_mul(n_inv)));
+        }
+        let one = 0u128.wrapping_sub(n) % n;
+        let mut m = Montgomery { n, n_inv, one, r2: one };
+        for _ in 0..128 {
+            m.r2 = m.add(m.r2, m.r2);
+        }
+
+        m
+    }
+
+    // REDC : (hi⋅2¹²⁸ + lo)⋅R⁻¹ mod n
+    fn reduce(&self, hi: u128, lo: u128) -> u128 {
+        let m = lo.wrapping_mul(self.n_inv);
+        let (mn_hi, _) = mul_wide(m, self.n);
+        if hi >= mn_hi { hi - mn_hi } else { hi.wrapping_sub(mn_hi).wrapping_add(self.n) }
+    }
+
+    fn mul(&self, a: u128, b: u128) -> u128 {
+        let (hi, lo) = mul_wide(a, b);
+        self.reduce(hi, lo)
+    }
+
+    fn add(&self, a: u128, b: u128) -> u128 {
+        let (sum, overflow) = a.overflowing_add(b);
+        if overflow || sum >= self.n { sum.wrapping_sub(self.n) } else { sum }
+    }
+
+    fn sub(&self, a: u128, b: u128) -> u128 {
+        if a >= b { a - b } else { a.wrapping_sub(b).wrapping_add(self.n) }
+    }
+
+    // x / 2 mod n (n impair), valable aussi sous forme de Montgomery
+    fn half(&self, x: u128) -> u128 {
+        if x.is_multiple_of(2) { x >> 1 } else { (x >> 1) + (self.n >> 1) + 1 }
+    }
+
+    // Entier signé de petite valeur absolue vers la forme de Montgomery
+    fn signed(&self, a: i128) -> u128 {
+        let a = if a < 0 { self.n - a.unsigned_abs() % self.n } else { a as u128 };
+        self.to_montgomery(a)
+    }
+
+    fn to_montgomery(&self, a: u128) -> u128 {
+        self.mul(a % self.n, self.r2)
+    }
+
+    fn pow(&self, mut base: u128, mut exponent: u128) -> u128 {
+        let mut result = self.one;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+// Division par les petits nombres premiers : None si le test n'est pas concluant
+fn small_prime_check(n: u128) -> Option<bool> {
+    if n < 2 { return Some(false); }
+    for p in SMALL_PRIMES {
+        if n == p { return Some(true); }
+        if n.is_multiple_of(p) { return Some(false); }
+    }
+    None
+}
+
+// Symbole de Jacobi (a/n), n impair
+fn jacobi(mut a: u128, mut n: u128) -> i32 {
+    a %= n;
+    let mut result = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 { result = -result; }
+        }
+        (a, n) = (n, a);
+        if a % 4 == 3 && n % 4 == 3 { result = -result; }
+        a %= n;
+    }
+    if n == 1 { result } else { 0 }
+}
+
+impl Modulus for Montgomery {
+    type Element = u128;
+    type Exponent = u128;
+
+    fn one(&self) -> u128 {
+        self.one
+    }
+
+    fn minus_one(&self) -> u128 {
+        self.to_montgomery(self.n - 1)
+    }
+
+    fn element(&self, a: u64) -> u128 {
+        self.to_montgomery(a as u128)
+    }
+
+    fn mul_mod(&self, a: &u128, b: &u128) -> u128 {
+        self.mul(*a, *b)
+    }
+
+    fn pow_mod(&self, base: &u128, exponent: &u128) -> u128 {
+        self.pow(*base, *exponent)
+    }
+
+    fn odd_part(&self) -> (u128, u32) {
+        let s = (self.n - 1).trailing_zeros();
+        ((self.n - 1) >> s, s)
+    }
+}
+
+// Test de Lucas fort avec les paramètres de Selfridge : D premier de 5, -7, 9, -11…
+// tel que (D/n) = -1, P = 1, Q = (1 - D) / 4. n doit être impair et non carré.
+fn strong_lucas_probable_prime(mont: &Montgomery) -> bool {
+    let n = mont.n;
+    let mut d: i128 = 5;
+    loop {
+        let a = if d < 0 { n - d.unsigned_abs() % n } else { d as u128 };
+        match jacobi(a, n) {
+            -1 => break,
+            0 if d.unsigned_abs() != n => return false,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+    let (big_d, q) = (mont.signed(d), mont.signed((1 - d) / 4));
+
+    // n + 1 = k⋅2^s, k impair ; U_k et V_k par la méthode binaire
+    let (s, k) = ((n + 1).trailing_zeros(), (n + 1) >> (n + 1).trailing_zeros());
+    let (mut u, mut v, mut q_k) = (mont.one, mont.one, q);
+    for bit in (0..127 - k.leading_zeros()).rev() {
+        (u, v) = (mont.mul(u, v), mont.sub(mont.mul(v, v), mont.add(q_k, q_k)));
+        q_k = mont.mul(q_k, q_k);
+        if k >> bit & 1 == 1 {
+            (u, v) = (mont.half(mont.add(u, v)), mont.half(mont.add(mont.mul(big_d, u), v)));
+            q_k = mont.mul(q_k, q);
+        }
+    }
+    if u == 0 { return true; }
+    for _ in 0..s {
+        if v == 0 { return true; }
+        v = mont.sub(mont.mul(v, v), mont.add(q_k, q_k));
+        q_k = mont.mul(q_k, q_k);
+    }
+    false
+}
+
+// Résultat certain sous PSI_13 (≈ 3,3⋅10²⁴, soit 2⁸¹) : Miller-Rabin sur les 13 premières bases.
+// Au-delà, le test est probabiliste : Baillie-PSW (Miller-Rabin en base 2 puis Lucas fort)
+// n'a aucun contre-exemple connu, mais aucune preuve n'en exclut sous 2¹²⁸.
+// Un `true` au-dessus de PSI_13 signifie donc « premier probable ».
+fn is_prime(n: u128) -> bool {
+    if n <= u64::MAX as u128 {
+        return prime_factor::is_prime(n as u64);
+    }
+    if let Some(result) = small_prime_check(n) {
+        return result;
+    }
+    let mont = Montgomery::new(n);
+    if n < PSI_13 {
+        return SMALL_PRIMES[..13].iter().all(|a| strong_probable_prime(&mont, *a as u64));
+    }
+    let root = n.isqrt();
+    strong_probable_prime(&mont, 2) && root * root != n && strong_lucas_probable_prime(&mont)
+}
+
+// Plus petit nombre premier strictement supérieur à n, None s'il dépasse u128
+fn next_prime(n: u128) -> Option<u128> {
+    if n < 2 { return Some(2); }
+    let mut candidate = if n.is_multiple_of(2) { n.checked_add(1)? } else { n.checked_add(2)? };
+    while !is_prime(candidate) {
+        candidate = candidate.checked_add(2)?;
+    }
+    Some(candidate)
+}
+
+// Variante de Brent du rho de Pollard, n doit être impair et composé.
+// Retourne un diviseur non trivial de n.
+fn pollard_brent(n: u128) -> u128 {
+    let mont = Montgomery::new(n);
+    for c in 1.. {
+        let c = mont.to_montgomery(c);
+        let f = |x: u128| mont.add(mont.mul(x, x), c);
+        let (mut y, mut x, mut ys) = (mont.one, mont.one, mont.one);
+        let (mut r, mut q, mut g) = (1u128, mont.one, 1u128);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BRENT_BATCH.min(r - k) {
+                    y = f(y);
+                    q = mont.mul(q, x.abs_diff(y));
+                }
+                g = gcd(q, n);
+                k += BRENT_BATCH;
+            }
+            r *= 2;
+        }
+        // Le lot a englobé tous les facteurs : reprendre pas à pas depuis la sauvegarde
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 { break; }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+// Décomposition en facteurs premiers triée : (facteur, exposant)
+fn factorize(mut n: u128) -> Vec<(u128, u32)> {
+    if n <= u64::MAX as u128 {
+        return prime_factor::factorize(n as u64).into_iter().map(|(p, e)| (p as u128, e)).collect();
+    }
+
+    let mut primes = Vec::<u128>::new();
+    let mut divisor = 2;
+    while divisor < TRIAL_LIMIT && divisor * divisor <= n {
+        while n.is_multiple_of(divisor) {
+            primes.push(divisor);
+            n /= divisor;
+        }
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    let mut composites = vec![n];
+    while let Some(m) = composites.pop() {
+        if m == 1 { continue; }
+        if is_prime(m) {
+            primes.push(m);
+        } else {
+            let d = pollard_brent(m);
+            composites.push(d);
+            composites.push(m / d);
+        }
+    }
+    primes.sort();
+
+    primes.into_iter().fold(Vec::new(), |mut acc: Vec<(u128, u32)>, p| {
+        match acc.last_mut() {
+            Some((last, exp)) if *last == p => *exp += 1,
+            _ => acc.push((p, 1)),
+        }
+        acc
+    })
+}
+
+#[test]
+fn wide_multiplication() {
+    assert_eq!(mul_wide(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+    assert_eq!(mul_wide(1 << 64, 1 << 64), (1, 0));
+    assert_eq!(mul_wide(123, 456), (0, 56_088));
+}
+#[test]
+fn montgomery_round_trip() {
+    let n = (1u128 << 127) - 1;
+    let mont = Montgomery::new(n);
+    let a = mont.to_montgomery(12_345_678_901_234_567_890);
+    assert_eq!(mont.reduce(0, a), 12_345_678_901_234_567_890);
+    assert_eq!(mont.reduce(0, mont.pow(a, n - 1)), 1);
+}
+#[test]
+fn small_numbers() {
+    let primes = (0..100).filter(|n| is_prime(*n)).collect::<Vec<u128>>();
+    assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97]);
+}
+#[test]
+fn strong_pseudoprimes_are_rejected() {
+    assert!(!is_prime(561));
+    assert!(!is_prime(3_215_031_751));
+    assert!(!is_prime(3_825_123_056_546_413_051));
+    assert!(!is_prime(318_665_857_834_031_151_167_461));
+    assert!(!is_prime(PSI_13));
+}
+#[test]
+fn deterministic_below_psi_13() {
+    // Partie démontrée exacte : même verdict que la factorisation complète
+    for n in PSI_13 - 100..PSI_13 {
+        assert_eq!(is_prime(n), factorize(n) == [(n, 1)], "{n}");
+    }
+}
+#[test]
+fn strong_lucas_pseudoprimes() {
+    // Pseudo-premiers de Lucas forts : écartés par Miller-Rabin en base 2
+    for n in [5459, 5777, 10_877, 16_109, 18_971] {
+        let mont = Montgomery::new(n);
+        assert!(strong_lucas_probable_prime(&mont));
+        assert!(!strong_probable_prime(&mont, 2));
+    }
+    // Pseudo-premiers forts en base 2 : écartés par Lucas
+    for n in [2047, 3_215_031_751, PSI_13] {
+        let mont = Montgomery::new(n);
+        assert!(strong_probable_prime(&mont, 2));
+        assert!(!strong_lucas_probable_prime(&mont));
+    }
+}
+#[test]
+fn jacobi_symbols() {
+    assert_eq!(jacobi(1001, 9907), -1);
+    assert_eq!(jacobi(19, 45), 1);
+    assert_eq!(jacobi(8, 21), -1);
+    assert_eq!(jacobi(5, 21), 1);
+    assert_eq!(jacobi(21, 21), 0);
+}
+#[test]
+fn primes_above_psi_13() {
+    let primes = (1_000_000_000_000_000_000_000_000_000_000..1_000_000_000_000_000_000_000_000_001_000)
+        .filter(|n| is_prime(*n))
+        .collect::<Vec<u128>>();
+    assert_eq!(primes, [
+        1_000_000_000_000_000_000_000_000_000_057, 1_000_000_000_000_000_000_000_000_000_099,
+        1_000_000_000_000_000_000_000_000_000_211, 1_000_000_000_000_000_000_000_000_000_231,
+        1_000_000_000_000_000_000_000_000_000_271, 1_000_000_000_000_000_000_000_000_000_469,
+        1_000_000_000_000_000_000_000_000_000_529, 1_000_000_000_000_000_000_000_000_000_577,
+        1_000_000_000_000_000_000_000_000_000_651, 1_000_000_000_000_000_000_000_000_000_687,
+        1_000_000_000_000_000_000_000_000_000_709, 1_000_000_000_000_000_000_000_000_000_751,
+        1_000_000_000_000_000_000_000_000_000_969,
+    ]);
+    let below_2_127 = ((1 << 127) - 1000..1 << 127).filter(|n| is_prime(*n)).count();
+    assert_eq!(below_2_127, 12);
+    assert!(!is_prime(1_329_236_869_339_117_470_509_617_537_493_239_699));
+    assert!(!is_prime(10_000_000_000_000_000_051 * 10_000_000_000_000_000_051));
+}
+#[test]
+fn large_primes() {
+    assert!(is_prime(18_446_744_073_709_551_557));
+    assert!(is_prime((1 << 89) - 1));
+    assert!(is_prime((1 << 127) - 1));
+    assert!(is_prime(u128::MAX - 158));
+    assert!(!is_prime(u128::MAX));
+}
+#[test]
+fn next_primes() {
+    assert_eq!(next_prime(0), Some(2));
+    assert_eq!(next_prime(2), Some(3));
+    assert_eq!(next_prime(13), Some(17));
+    assert_eq!(next_prime(1 << 64), Some(18_446_744_073_709_551_629));
+    assert_eq!(next_prime(1 << 127), Some(170_141_183_460_469_231_731_687_303_715_884_105_757));
+    assert_eq!(next_prime(u128::MAX - 160), Some(u128::MAX - 158));
+    assert_eq!(next_prime(u128::MAX - 158), None);
+}
+#[test]
+fn factorize_small() {
+    assert_eq!(factorize(0), vec![]);
+    assert_eq!(factorize(1), vec![]);
+    assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(factorize(97), vec![(97, 1)]);
+}
+#[test]
+fn factorize_mersenne_67() {
+    assert_eq!(factorize((1 << 67) - 1), vec![(193_707_721, 1), (761_838_257_287, 1)]);
+}
+#[test]
+fn factorize_thirty_digit_semiprime() {
+    let n = 1_000_000_000_030_000_005_100_000_000_153;
+    assert_eq!(factorize(n), vec![(100_000_000_003, 1), (10_000_000_000_000_000_051, 1)]);
+}
+#[test]
+fn factorize_with_exponents() {
+    let p: u128 = 100_000_000_003;
+    let n = 1024 * 243 * p * p;
+    assert_eq!(factorize(n), vec![(2, 10), (3, 5), (p, 2)]);
+}
@@ -0,0 +1,105 @@
+// https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test
+//
+// Un seul test de Miller-Rabin pour toutes les tailles d'entiers : u64 (prime_factor),
+// u128 sous forme de Montgomery (big_primes) et BigUint (Diffie-Hellman). Chaque type
+// fournit seulement son arithmétique modulo n.
+
+use crate::numbers::big_uint::BigUint;
+
+// Petits nombres premiers : bases des témoins et diviseurs d'essai
+pub(crate) const SMALL_PRIMES: [u64; 24] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89];
+
+// Arithmétique modulo un entier n impair, les éléments étant dans la représentation
+// propre à l'implémentation (reste ordinaire, forme de Montgomery…)
+pub(crate) trait Modulus {
+    type Element: PartialEq;
+    type Exponent;
+
+    fn one(&self) -> Self::Element;
+    fn minus_one(&self) -> Self::Element;
+    fn element(&self, a: u64) -> Self::Element;
+    fn mul_mod(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn pow_mod(&self, base: &Self::Element, exponent: &Self::Exponent) -> Self::Element;
+    // n - 1 = d⋅2^s, renvoie (d, s)
+    fn odd_part(&self) -> (Self::Exponent, u32);
+}
+
+// n est-il un nombre premier probable fort en base a ?
+pub(crate) fn strong_probable_prime<M: Modulus>(n: &M, a: u64) -> bool {
+    let (d, s) = n.odd_part();
+    let minus_one = n.minus_one();
+    let mut x = n.pow_mod(&n.element(a), &d);
+    if x == n.one() || x == minus_one {
+        return true;
+    }
+    (1..s).any(|_| {
+        x = n.mul_mod(&x, &x);
+        x == minus_one
+    })
+}
+
+impl Modulus for BigUint {
+    type Element = BigUint;
+    type Exponent = BigUint;
+
+    fn one(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn minus_one(&self) -> BigUint {
+        self.checked_sub(&BigUint::one()).unwrap()
+    }
+
+    fn element(&self, a: u64) -> BigUint {
+        BigUint::from(a as u128).div_rem(self).1
+    }
+
+    fn mul_mod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b).div_rem(self).1
+    }
+
+    fn pow_mod(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        base.modpow(exponent, self)
+    }
+
+    fn odd_part(&self) -> (BigUint, u32) {
+        let (mut d, mut s) = (self.minus_one(), 0);
+        while d.is_even() {
+            d = d.div_rem_small(2).0;
+            s += 1;
+        }
+        (d, s)
+    }
+}
+
+// Miller-Rabin avec les 16 premières bases : déterministe au moins sous 3,3·10²⁴,
+// probabiliste au-delà (erreur inférieure à 4⁻¹⁶).
+pub(crate) fn is_probable_prime(n: &BigUint) -> bool {
+    for prime in SMALL_PRIMES {
+        if *n == BigUint::from(prime as u128) { return true; }
+        if n.div_rem_small(prime).1 == 0 { return false; }
+    }
+    if *n < BigUint::from(2) { return false; }
+
+    SMALL_PRIMES[..16].iter().all(|a| strong_probable_prime(n, *a))
+}
+
+#[test]
+fn probable_primes() {
+    assert!(is_probable_prime(&BigUint::from(2)));
+    assert!(is_probable_prime(&BigUint::from(1_000_000_007)));
+    assert!(is_probable_prime(&BigUint::from((1 << 127) - 1)));
+    assert!(!is_probable_prime(&BigUint::from(1)));
+    assert!(!is_probable_prime(&BigUint::from(3_215_031_751)));
+    assert!(!is_probable_prime(&BigUint::from(318_665_857_834_031_151_167_461)));
+    assert!(!is_probable_prime(&BigUint::from(3_317_044_064_679_887_385_961_981)));
+    assert!(!is_probable_prime(&BigUint::from(((1 << 61) - 1) * ((1 << 31) - 1))));
+}
+#[test]
+fn same_witnesses_for_every_size() {
+    // 2047 = 23 × 89 est pseudo-premier fort en base 2 mais pas en base 3
+    assert!(strong_probable_prime(&2047u64, 2));
+    assert!(!strong_probable_prime(&2047u64, 3));
+    assert!(strong_probable_prime(&BigUint::from(2047), 2));
+    assert!(!strong_probable_prime(&BigUint::from(2047), 3));
+}
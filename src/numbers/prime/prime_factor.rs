@@ -1,23 +1,199 @@
-fn factors(n: u64) -> Vec<u64> {
-    let primes: Vec<u64> = vec![2, 3, 5, 7, 11, 13, 17, 23, 461, 9539, 894119];
-    let mut fact = Vec::<u64>::new();
-    let mut n2 = n;
+// https://en.wikipedia.org/wiki/Wheel_factorization
+// https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm
+
+use crate::numbers::prime::miller_rabin::{strong_probable_prime, Modulus};
+
+// Roue 2⋅3⋅5 : écarts successifs entre les candidats premiers avec 30 à partir de 7
+const WHEEL: [u64; 8] = [4, 2, 4, 2, 4, 6, 2, 6];
+// Au-delà de cette borne, le reste non factorisé passe par Miller-Rabin et rho
+const WHEEL_LIMIT: u64 = 1 << 16;
+// Bases suffisantes pour un test de Miller-Rabin déterministe sur tout u64
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+pub(crate) fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+pub(crate) fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Modulus for u64 {
+    type Element = u64;
+    type Exponent = u64;
+
+    fn one(&self) -> u64 {
+        1
+    }
+
+    fn minus_one(&self) -> u64 {
+        self - 1
+    }
+
+    fn element(&self, a: u64) -> u64 {
+        a % self
+    }
+
+    fn mul_mod(&self, a: &u64, b: &u64) -> u64 {
+        mul_mod(*a, *b, *self)
+    }
+
+    fn pow_mod(&self, base: &u64, exponent: &u64) -> u64 {
+        pow_mod(*base, *exponent, *self)
+    }
+
+    fn odd_part(&self) -> (u64, u32) {
+        let s = (self - 1).trailing_zeros();
+        ((self - 1) >> s, s)
+    }
+}
+
+pub(crate) fn is_prime(n: u64) -> bool {
+    if n < 2 { return false; }
+    for p in MILLER_RABIN_BASES {
+        if n == p { return true; }
+        if n.is_multiple_of(p) { return false; }
+    }
+    MILLER_RABIN_BASES.iter().all(|a| strong_probable_prime(&n, *a))
+}
+
+// Rho de Pollard (variante de Brent), n doit être impair et composé
+fn pollard_rho(n: u64) -> u64 {
+    for c in 1.. {
+        // Addition en u128 : x² mod n + c peut dépasser u64 pour n proche de u64::MAX
+        let f = |x: u64| ((mul_mod(x, x, n) as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut ys) = (2, 2, 2);
+        let (mut r, mut q, mut g) = (1, 1, 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..(r - k).min(128) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += 128;
+            }
+            r *= 2;
+        }
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 { break; }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
 
-    if n < 2 {
-        return vec![];
+// Décomposition en facteurs premiers sous forme (premier, exposant), triée.
+// 0 et 1 n'ont pas de facteur.
+pub(crate) fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut primes = Vec::<(u64, u32)>::new();
+    if n < 2 { return primes; }
+
+    let mut divide = |n: &mut u64, p: u64| {
+        let mut exponent = 0;
+        while n.is_multiple_of(p) {
+            *n /= p;
+            exponent += 1;
+        }
+        if exponent > 0 { primes.push((p, exponent)); }
+    };
+    for p in [2, 3, 5] {
+        divide(&mut n, p);
+    }
+    let mut candidate = 7;
+    for step in WHEEL.iter().cycle() {
+        if candidate > WHEEL_LIMIT || candidate * candidate > n { break; }
+        divide(&mut n, candidate);
+        candidate += step;
     }
 
-    for prime in primes {
-        while n2 > 0 && n2 >= prime && n2 % prime == 0 {
-            fact.push(prime);
-            n2 /= prime;
+    let mut composites = vec![n];
+    let mut large = Vec::<u64>::new();
+    while let Some(m) = composites.pop() {
+        if m == 1 { continue; }
+        if is_prime(m) {
+            large.push(m);
+        } else {
+            let d = pollard_rho(m);
+            composites.push(d);
+            composites.push(m / d);
         }
     }
-    if fact.iter().product::<u64>() == n {
-        fact
-    } else {
-        vec![]
+    large.sort();
+    for p in large {
+        match primes.last_mut() {
+            Some((last, exponent)) if *last == p => *exponent += 1,
+            _ => primes.push((p, 1)),
+        }
     }
+
+    primes
+}
+
+fn factors(n: u64) -> Vec<u64> {
+    factorize(n).into_iter()
+        .flat_map(|(p, exponent)| std::iter::repeat_n(p, exponent as usize))
+        .collect()
+}
+
+// Nombre de diviseurs : ∏(e + 1)
+pub(crate) fn divisor_count(n: u64) -> u64 {
+    if n == 0 { return 0; }
+    factorize(n).iter().map(|(_, e)| *e as u64 + 1).product()
+}
+
+// Somme des diviseurs : ∏(p^(e+1) - 1) / (p - 1), en u128 car σ(n) peut dépasser u64
+pub(crate) fn sigma(n: u64) -> u128 {
+    if n == 0 { return 0; }
+    factorize(n).iter()
+        .map(|(p, e)| {
+            let p = *p as u128;
+            (0..*e).fold((1, 1), |(sum, power), _| (sum + power * p, power * p)).0
+        })
+        .product()
+}
+
+// Indicatrice d'Euler : n ∏(1 - 1/p)
+pub(crate) fn totient(n: u64) -> u64 {
+    if n == 0 { return 0; }
+    factorize(n).iter().fold(n, |acc, (p, _)| acc / p * (p - 1))
+}
+
+// Fonction de Möbius : 0 si n a un facteur carré, sinon (-1)^k pour k facteurs premiers
+pub(crate) fn mobius(n: u64) -> i8 {
+    if n == 0 { return 0; }
+    let factors = factorize(n);
+    if factors.iter().any(|(_, e)| *e > 1) { 0 }
+    else if factors.len().is_multiple_of(2) { 1 }
+    else { -1 }
 }
 
 #[test]
@@ -103,3 +279,78 @@ fn factors_include_a_large_prime() {
     let expected = [11, 9539, 894119];
     assert_eq!(factors, expected);
 }
+
+#[test]
+fn factors_beyond_the_old_table() {
+    assert_eq!(factors(29 * 31 * 37), [29, 31, 37]);
+    assert_eq!(factors(1_000_000_007), [1_000_000_007]);
+}
+
+#[test]
+fn factors_of_large_semiprime() {
+    // 2^32 + 15 et 2^31 - 1 sont premiers
+    let factors = factors(4_294_967_311 * 2_147_483_647);
+    assert_eq!(factors, [2_147_483_647, 4_294_967_311]);
+}
+
+#[test]
+fn factors_of_semiprime_near_u64_max() {
+    // x² mod n + c dépasse u64 dans rho pour un tel n
+    assert_eq!(factors(4_294_967_279 * 4_294_967_291), [4_294_967_279, 4_294_967_291]);
+    let divisor = pollard_rho(4_294_967_279 * 4_294_967_291);
+    assert!(divisor == 4_294_967_279 || divisor == 4_294_967_291);
+}
+
+#[test]
+fn factors_of_largest_u64() {
+    assert_eq!(factors(u64::MAX), [3, 5, 17, 257, 641, 65_537, 6_700_417]);
+    assert_eq!(factors(18_446_744_073_709_551_557), [18_446_744_073_709_551_557]);
+}
+
+#[test]
+fn factorize_with_exponents() {
+    assert_eq!(factorize(0), []);
+    assert_eq!(factorize(1), []);
+    assert_eq!(factorize(360), [(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(factorize(1 << 63), [(2, 63)]);
+    assert_eq!(factorize(4_294_967_291 * 4_294_967_291), [(4_294_967_291, 2)]);
+}
+
+#[test]
+fn primality() {
+    let primes = (0..50).filter(|n| is_prime(*n)).collect::<Vec<u64>>();
+    assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]);
+    assert!(!is_prime(3_215_031_751));
+    assert!(!is_prime(3_825_123_056_546_413_051));
+}
+
+#[test]
+fn arithmetic_functions() {
+    assert_eq!(divisor_count(1), 1);
+    assert_eq!(divisor_count(360), 24);
+    assert_eq!(sigma(1), 1);
+    assert_eq!(sigma(28), 56);
+    assert_eq!(sigma(360), 1170);
+    assert_eq!(totient(1), 1);
+    assert_eq!(totient(36), 12);
+    assert_eq!(totient(1_000_000_007), 1_000_000_006);
+    assert_eq!(mobius(1), 1);
+    assert_eq!(mobius(30), -1);
+    assert_eq!(mobius(6), 1);
+    assert_eq!(mobius(12), 0);
+}
+
+#[test]
+fn arithmetic_functions_against_brute_force() {
+    for n in 1..=500u64 {
+        let divisors = (1..=n).filter(|d| n % d == 0).collect::<Vec<u64>>();
+        assert_eq!(divisor_count(n), divisors.len() as u64);
+        assert_eq!(sigma(n), divisors.iter().sum::<u64>() as u128);
+        assert_eq!(totient(n), (1..=n).filter(|k| gcd(*k, n) == 1).count() as u64);
+    }
+}
+
+#[test]
+fn sigma_does_not_overflow() {
+    assert_eq!(sigma(u64::MAX), 31_421_980_989_189_888_768);
+}
@@ -1,28 +1,163 @@
-use std::collections::HashMap;
+// https://fr.wikipedia.org/wiki/Crible_d%27%C3%89ratosth%C3%A8ne#Crible_segment%C3%A9
+//
+// Crible segmenté ne conservant que les impairs, un bit par nombre (1 = composé).
+// La mémoire se limite au segment courant et aux nombres premiers de base jusqu'à
+// la racine de la borne haute du segment : environ 10^4 entiers pour atteindre 10^10.
+const SEGMENT_WORDS: usize = 1 << 12;
+const SEGMENT_SPAN: u64 = (SEGMENT_WORDS * 64 * 2) as u64; // impairs et pairs couverts
 
-/*************************************************************** */
+pub(crate) struct Primes {
+    base: Vec<u64>,  // nombres premiers impairs <= base_limit
+    base_limit: u64,
+    low: u64,        // début (pair) du segment courant, le bit i représente low + 2i + 1
+    start: u64,
+    end: u64,        // borne incluse, u64::MAX pour un itérateur infini
+    bits: Vec<u64>,
+    index: usize,
+    two: bool,       // 2 reste à émettre
+}
 
-fn primes_generated(start: u32, end: u32) -> Vec<u32> {
-    let mut num_naturals = HashMap::with_capacity((end - start) as usize);
-    for num in start..=end {
-        num_naturals.insert(num, true);
+impl Primes {
+    pub(crate) fn new() -> Self {
+        Primes::between(2, u64::MAX)
+    }
+
+    pub(crate) fn between(start: u64, end: u64) -> Self {
+        let mut primes = Primes {
+            base: Vec::new(),
+            base_limit: 2,
+            low: start & !1,
+            start,
+            end,
+            bits: vec![0; SEGMENT_WORDS],
+            index: 0,
+            two: start <= 2 && 2 <= end,
+        };
+        if start <= end {
+            primes.sieve_segment();
+        }
+
+        primes
+    }
+
+    // Nombres premiers de base jusqu'à limit, criblés eux aussi par segments à partir de ceux
+    // déjà connus (étendus d'abord jusqu'à la racine de limit si besoin).
+    fn extend_base(&mut self, limit: u64) {
+        let limit = limit.max(self.base_limit.saturating_mul(2));
+        if limit.isqrt() > self.base_limit {
+            self.extend_base(limit.isqrt());
+        }
+        let mut low = self.base_limit + 1;
+        while low <= limit {
+            let high = low.saturating_add(SEGMENT_SPAN - 1).min(limit);
+            let mut composite = vec![false; (high - low + 1) as usize];
+            for p in &self.base {
+                match p.checked_mul(*p) {
+                    Some(square) if square <= high => {
+                        let first = square.max(low.div_ceil(*p) * p);
+                        (first..=high).step_by(*p as usize).for_each(|j| composite[(j - low) as usize] = true);
+                    },
+                    _ => break,
+                }
+            }
+            let found = (low.max(3)..=high).filter(|n| n % 2 == 1 && !composite[(n - low) as usize]).collect::<Vec<u64>>();
+            self.base.extend(found);
+            low = high + 1;
+        }
+        self.base_limit = limit;
+    }
+
+    fn sieve_segment(&mut self) {
+        let high = self.low.saturating_add(SEGMENT_SPAN);
+        let root = high.isqrt() + 1;
+        if root > self.base_limit {
+            self.extend_base(root);
+        }
+        self.bits.fill(0);
+        self.index = 0;
+        let total_bits = (SEGMENT_WORDS * 64) as u64;
+        for p in &self.base {
+            let square = match p.checked_mul(*p) {
+                Some(square) if square < high => square,
+                _ => break,
+            };
+            // Premier multiple impair de p dans le segment, sans descendre sous p²
+            let Some(mut multiple) = (self.low + 1).div_ceil(*p).checked_mul(*p) else { continue };
+            multiple = multiple.max(square);
+            if multiple % 2 == 0 {
+                match multiple.checked_add(*p) {
+                    Some(odd) => multiple = odd,
+                    None => continue,
+                }
+            }
+            let mut bit = (multiple - self.low - 1) / 2;
+            while bit < total_bits {
+                self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+                bit += p;
+            }
+        }
+        if self.low == 0 {
+            self.bits[0] |= 1; // 1 n'est pas premier
+        }
+        // Écarter les impairs sous le début demandé (premier segment uniquement)
+        let mut bit = 0;
+        while self.low + 2 * bit + 1 < self.start && bit < total_bits {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+            bit += 1;
+        }
     }
-    let sqrt_end = (end as f64).sqrt() as u32;
-    for i in start..=sqrt_end {
-        if num_naturals[&i] {
-            for j in ((i.pow(2))..=end).step_by(i as usize) {
-                num_naturals.insert(j, false);
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.two {
+            self.two = false;
+            return Some(2);
+        }
+        while self.low <= self.end {
+            while self.index < SEGMENT_WORDS * 64 {
+                let word = !self.bits[self.index / 64] >> (self.index % 64);
+                if word == 0 {
+                    self.index = (self.index / 64 + 1) * 64;
+                    continue;
+                }
+                self.index += word.trailing_zeros() as usize;
+                // Le dernier segment peut déborder de u64 : fin de l'itération
+                let value = match self.low.checked_add(2 * self.index as u64 + 1) {
+                    Some(value) if value <= self.end => value,
+                    _ => {
+                        self.low = u64::MAX;
+                        self.end = 0;
+                        return None;
+                    },
+                };
+                self.index += 1;
+                return Some(value);
             }
+            self.low = match self.low.checked_add(SEGMENT_SPAN) {
+                Some(low) if low <= self.end => low,
+                _ => break,
+            };
+            self.sieve_segment();
         }
+        self.end = 0;
+        self.low = u64::MAX;
+        None
     }
-    let mut v = num_naturals.into_iter().filter(|n| n.1).map(|m| m.0).collect::<Vec<u32>>();
-    v.sort();
-    v
 }
 
+fn primes_generated(start: u32, end: u32) -> Vec<u32> {
+    Primes::between(start as u64, end as u64).map(|p| p as u32).collect()
+}
+
+// Borne supérieure du n-ième nombre premier (indice k = n + 1) issue du théorème des
+// nombres premiers : p_k < k(ln k + ln ln k) pour k >= 6.
 fn nth(n: u32) -> u32 {
-    let rslt = primes_generated(2, 105_000);
-    rslt[n as usize]
+    let k = n as f64 + 1.0;
+    let bound = if k < 6.0 { 13 } else { (k * (k.ln() + k.ln().ln())).ceil() as u64 };
+    Primes::between(2, bound).nth(n as usize).unwrap() as u32
 }
 
 #[test]
@@ -47,9 +182,66 @@ fn sixth_prime() {
 }
 
 #[test]
-
 fn big_prime() {
     let output = nth(10000);
     let expected = 104743;
     assert_eq!(output, expected);
 }
+
+#[test]
+fn millionth_prime() {
+    assert_eq!(nth(999_999), 15_485_863);
+}
+
+#[test]
+fn primes_in_a_range() {
+    assert_eq!(primes_generated(10, 30), vec![11, 13, 17, 19, 23, 29]);
+    assert_eq!(primes_generated(0, 2), vec![2]);
+    assert_eq!(primes_generated(14, 16), vec![]);
+    assert_eq!(primes_generated(20, 10), vec![]);
+}
+
+#[test]
+fn count_primes_below_ten_million() {
+    assert_eq!(Primes::new().take_while(|p| *p < 10_000_000).count(), 664_579);
+}
+
+#[test]
+fn primes_across_segments() {
+    let expected = [1_048_571, 1_048_573, 1_048_583, 1_048_589];
+    let primes = Primes::between(1_048_570, 1_048_590).collect::<Vec<u64>>();
+    assert_eq!(primes, expected);
+}
+
+#[test]
+fn primes_past_ten_billion() {
+    let primes = Primes::between(10_000_000_000, 10_000_000_100).collect::<Vec<u64>>();
+    let expected = [10_000_000_019, 10_000_000_033, 10_000_000_061, 10_000_000_069, 10_000_000_097];
+    assert_eq!(primes, expected);
+}
+
+#[test]
+#[ignore]
+fn count_primes_below_ten_billion() {
+    assert_eq!(Primes::new().take_while(|p| *p < 10_000_000_000).count(), 455_052_511);
+}
+
+#[test]
+fn base_primes_extended_by_segments() {
+    let mut primes = Primes::between(2, 2);
+    primes.extend_base(3_000_000);
+    assert_eq!(primes.base.len(), 216_815);
+    assert_eq!(primes.base[..5], [3, 5, 7, 11, 13]);
+    assert!(primes.base.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+#[ignore]
+fn primes_below_u64_max() {
+    let primes = Primes::between(u64::MAX - 199, u64::MAX).collect::<Vec<u64>>();
+    let expected = [
+        18_446_744_073_709_551_427, 18_446_744_073_709_551_437, 18_446_744_073_709_551_521,
+        18_446_744_073_709_551_533, 18_446_744_073_709_551_557,
+    ];
+    assert_eq!(primes, expected);
+}
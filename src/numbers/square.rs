@@ -1,16 +1,83 @@
+// https://en.wikipedia.org/wiki/Wheat_and_chessboard_problem
+// https://en.wikipedia.org/wiki/Geometric_series#Sum
 
-fn square(s: u32) -> u64 {
-    match s {
-        a if a < 1 || a > 64 => panic!("Square must be between 1 and 64"),
-        _ => 2u64.pow(s - 1)
+use super::{big_uint::BigUint, bcd::nombre::Nombre};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Error {
+    SquareOutOfBoard(u32),
+    EmptyBoard,
+    InvalidRatio,
+    Overflow,
+}
+
+// Plateau de `squares` cases : la case s porte ratio^(s-1) grains
+struct Board {
+    squares: u32,
+    ratio: u64,
+}
+
+impl Board {
+    fn new(squares: u32, ratio: u64) -> Result<Self, Error> {
+        if squares == 0 { return Err(Error::EmptyBoard); }
+        if ratio == 0 { return Err(Error::InvalidRatio); }
+        Ok(Board { squares, ratio })
+    }
+
+    fn chessboard() -> Self {
+        Board { squares: 64, ratio: 2 }
+    }
+
+    fn check(&self, s: u32) -> Result<(), Error> {
+        if s < 1 || s > self.squares { return Err(Error::SquareOutOfBoard(s)); }
+        Ok(())
+    }
+
+    fn square(&self, s: u32) -> Result<u128, Error> {
+        self.check(s)?;
+        (self.ratio as u128).checked_pow(s - 1).ok_or(Error::Overflow)
+    }
+
+    // Somme géométrique (r^n - 1) / (r - 1), r = 1 donne simplement n
+    fn total(&self) -> Result<u128, Error> {
+        if self.ratio == 1 { return Ok(self.squares as u128); }
+        let ratio = self.ratio as u128;
+        match ratio.checked_pow(self.squares) {
+            Some(power) => Ok((power - 1) / (ratio - 1)),
+            // La somme dépasse la dernière case r^(n-1) : si celle-ci déborde, inutile d'aller plus loin
+            None if ratio.checked_pow(self.squares - 1).is_none() => Err(Error::Overflow),
+            // r^n dépasse u128 alors que la somme peut encore y tenir (ex. 128 cases doublées)
+            None => self.big_total().to_u128().ok_or(Error::Overflow),
+        }
+    }
+
+    fn big_total(&self) -> BigUint {
+        if self.ratio == 1 { return BigUint::from(self.squares as u128); }
+        let power = BigUint::pow_small(self.ratio, self.squares);
+        let (sum, _) = power.checked_sub(&BigUint::one()).unwrap().div_rem_small(self.ratio - 1);
+        sum
+    }
+
+    fn square_nombre(&self, s: u32) -> Result<Nombre, Error> {
+        self.check(s)?;
+        Ok(BigUint::pow_small(self.ratio, s - 1).to_nombre())
+    }
+
+    // Même somme géométrique, exacte au-delà de u128
+    fn total_nombre(&self) -> Nombre {
+        self.big_total().to_nombre()
     }
 }
 
+fn square(s: u32) -> Result<u64, Error> {
+    Board::chessboard().square(s).map(|grains| grains as u64)
+}
+
 fn total() -> u64 {
-    (1..=64).map(square).sum()
+    Board::chessboard().total().unwrap() as u64
 }
 
-fn process_square_case(input: u32, expected: u64) { assert_eq!(square(input), expected); }
+fn process_square_case(input: u32, expected: u64) { assert_eq!(square(input), Ok(expected)); }
 #[test] fn one() { process_square_case(1, 1); }
 #[test] fn two() { process_square_case(2, 2); }
 #[test] fn three() { process_square_case(3, 4); }
@@ -18,6 +85,42 @@ fn process_square_case(input: u32, expected: u64) { assert_eq!(square(input), ex
 #[test] fn sixteen() { process_square_case(16, 32_768); }
 #[test] fn thirty_two() { process_square_case(32, 2_147_483_648); }
 #[test] fn sixty_four() { process_square_case(64, 9_223_372_036_854_775_808); }
-#[test] #[should_panic(expected = "Square must be between 1 and 64")] fn square_0_raises_an_exception() { square(0); }
-#[test] #[should_panic(expected = "Square must be between 1 and 64")] fn square_greater_than_64_raises_an_exception() { square(65); }
-#[test] fn returns_the_total_number_of_grains_on_the_board() { assert_eq!(total(), 18_446_744_073_709_551_615); }
\ No newline at end of file
+#[test] fn square_0_raises_an_exception() { assert_eq!(square(0), Err(Error::SquareOutOfBoard(0))); }
+#[test] fn square_greater_than_64_raises_an_exception() { assert_eq!(square(65), Err(Error::SquareOutOfBoard(65))); }
+#[test] fn returns_the_total_number_of_grains_on_the_board() { assert_eq!(total(), 18_446_744_073_709_551_615); }
+#[test]
+fn invalid_boards() {
+    assert!(matches!(Board::new(0, 2), Err(Error::EmptyBoard)));
+    assert!(matches!(Board::new(64, 0), Err(Error::InvalidRatio)));
+}
+#[test]
+fn larger_boards_in_u128() {
+    let board = Board::new(128, 2).unwrap();
+    assert_eq!(board.square(128), Ok(1 << 127));
+    assert_eq!(board.total(), Ok(u128::MAX));
+    assert_eq!(Board::new(129, 2).unwrap().total(), Err(Error::Overflow));
+    assert_eq!(Board::new(129, 2).unwrap().square(129), Err(Error::Overflow));
+    assert_eq!(Board::new(u32::MAX, u64::MAX).unwrap().total(), Err(Error::Overflow));
+    assert_eq!(Board::new(u32::MAX, 2).unwrap().total(), Err(Error::Overflow));
+    // r^n déborde, la somme tient : 1 + (2⁶⁴ - 1) + (2⁶⁴ - 1)²
+    assert_eq!(Board::new(3, u64::MAX).unwrap().total(), Ok(u128::MAX - u64::MAX as u128 + 1));
+    assert_eq!(Board::new(u32::MAX, 2).unwrap().square(u32::MAX), Err(Error::Overflow));
+}
+#[test]
+fn other_ratios() {
+    let board = Board::new(10, 3).unwrap();
+    assert_eq!(board.square(10), Ok(19_683));
+    assert_eq!(board.total(), Ok(29_524));
+    let constant = Board::new(1000, 1).unwrap();
+    assert_eq!(constant.square(1000), Ok(1));
+    assert_eq!(constant.total(), Ok(1000));
+    assert_eq!(Board::new(20, 10).unwrap().total(), Ok(11_111_111_111_111_111_111));
+}
+#[test]
+fn beyond_u128_with_nombre() {
+    let board = Board::new(200, 2).unwrap();
+    assert_eq!(board.square_nombre(200), Ok(Nombre::from("803469022129495137770981046170581301261101496891396417650688")));
+    assert_eq!(board.total_nombre(), Nombre::from("1606938044258990275541962092341162602522202993782792835301375"));
+    assert_eq!(board.square_nombre(201), Err(Error::SquareOutOfBoard(201)));
+    assert_eq!(Board::chessboard().total_nombre(), Nombre::from("18446744073709551615"));
+}